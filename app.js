@@ -4,16 +4,110 @@ class PixelSortApp {
         this.sorter = new PixelSorter();
         this.currentAlgorithm = 'Horizontal';
         this.currentMode = 'Brightness';
+        this.reverse = false;
+        this.invertIntervals = false;
+        this.intervalMode = 'Threshold';
+        this.randomSeed = 1;
+        this.randomLength = 32;
+        this.passes = 1;
+        this.minSegment = 0;
+        this.maxSegment = 0;
         this.threshold = 0;
         this.hueShift = 0;
+        this.angle = 45;
+        this.opacity = 1;
+        this.normalize = false;
+        this.blurRadius = 0;
+        this.sharpenAmount = 0;
+        this.paletteColors = 0;
+        this.grainIntensity = 0;
+        this.grainSeed = 1;
+        this.channelShift = 0;
+        this.processScale = 1;
+        this.processScales = [1, 0.5, 0.25];
+        this.macros = [...PixelSorter.builtInMacros, ...this.loadSavedMacros()];
+        this.currentMacro = this.macros[0];
         this.originalImage = null;
+        this.sourceImage = null;
+        this.sourceFile = null;
+        this.sourceSaved = false;
         this.processedImage = null;
         this.iterationCount = 0;
+        this.iterationFrames = [];
+        this.exportFormats = [
+            { name: 'PNG', outputs: [{ type: 'image/png' }] },
+            { name: 'WebP 90', outputs: [{ type: 'image/webp', quality: 0.9 }] },
+            { name: 'WebP 75', outputs: [{ type: 'image/webp', quality: 0.75 }] },
+            { name: 'WebP 50', outputs: [{ type: 'image/webp', quality: 0.5 }] },
+            { name: 'JPEG 90', outputs: [{ type: 'image/jpeg', quality: 0.9 }] },
+            { name: 'JPEG 75', outputs: [{ type: 'image/jpeg', quality: 0.75 }] },
+            { name: 'PNG + JPEG', outputs: [{ type: 'image/png' }, { type: 'image/jpeg', quality: 0.9 }] }
+        ];
+        this.exportFormat = this.loadExportFormat();
+        this.saveSidecar = false;
+        this.animationRates = [1, 2, 5, 10];
+        this.animationFps = 2;
+        this.sweep = null;
+        this.comparisonLayouts = ['Side by Side', 'Stacked'];
+        this.comparisonLayout = this.comparisonLayouts[0];
+        this.watermarkPositions = ['Off', 'Bottom Right', 'Bottom Left', 'Top Right', 'Top Left'];
+        this.watermark = { position: 'Off', opacity: 0.6, scale: 0.15 };
+        this.watermarkImage = new Image();
+        this.watermarkImage.src = 'Harpy_ICON.png';
+        this.sessionIds = new Set();
         this.sessionId = this.generateSessionId();
         this.cameraStream = null;
+        this.loadToken = 0;
+        this.sortWorker = null;
+        this.workerUnavailable = false;
+        this.sorting = false;
+        this.imageQueue = [];
+        this.maxImageSize = { long: 1920, short: 1080 };
         
         this.initUI();
+        this.initDragAndDrop();
+        this.initClipboard();
         this.initSplashScreen();
+        this.restoreLastParams();
+    }
+
+    /**
+     * Give the session a human name; it prefixes every session-level download
+     */
+    renameSession() {
+        const input = prompt('Session name', this.sessionId);
+        if (input === null) return;
+
+        // Keep it a single safe filename component
+        const name = input.trim()
+            .replace(/[\\/:*?"<>|\s]+/g, '_')
+            .replace(/^[._]+|_+$/g, '')
+            .slice(0, 64);
+        if (!name) {
+            this.showStatus('Session name cannot be empty');
+            return;
+        }
+
+        this.sessionId = name;
+        document.getElementById('session-text').textContent = name;
+        this.showStatus(`Session renamed: ${name}`);
+    }
+
+    /**
+     * Every loaded image opens its own session, so each gets its original saved with it
+     */
+    startSession() {
+        // Loads within the same second would otherwise share a name
+        const base = this.generateSessionId();
+        let id = base;
+        for (let n = 2; this.sessionIds.has(id); n++) {
+            id = `${base}_${n}`;
+        }
+        this.sessionIds.add(id);
+
+        this.sessionId = id;
+        this.sourceSaved = false;
+        document.getElementById('session-text').textContent = id;
     }
 
     generateSessionId() {
@@ -49,17 +143,108 @@ class PixelSortApp {
 
         cameraBtn.addEventListener('click', () => this.openCamera());
         uploadBtn.addEventListener('click', () => this.openFileDialog());
+        document.getElementById('url-btn').addEventListener('click', () => this.loadImageFromUrl());
         saveBtn.addEventListener('click', () => this.saveImage());
         iterateBtn.addEventListener('click', () => this.iterateImage());
+        document.getElementById('file-input').addEventListener('change', (e) => this.handleFileSelect(e));
 
         // Edit phase controls
         document.getElementById('threshold-slider').addEventListener('input', (e) => this.updateThreshold(e));
         document.getElementById('hue-slider').addEventListener('input', (e) => this.updateHue(e));
+        document.getElementById('angle-slider').addEventListener('input', (e) => this.updateAngle(e));
+        document.getElementById('opacity-slider').addEventListener('input', (e) => this.updateOpacity(e));
+        document.getElementById('interval-btn').addEventListener('click', () => this.cycleIntervalMode());
+        document.getElementById('seed-btn').addEventListener('click', () => this.reseedIntervals());
+        document.getElementById('passes-slider').addEventListener('input', (e) => this.updatePasses(e));
+        document.getElementById('random-length-slider').addEventListener('input', (e) => this.updateRandomLength(e));
+        document.getElementById('min-segment-slider').addEventListener('input', (e) => this.updateMinSegment(e));
+        document.getElementById('max-segment-slider').addEventListener('input', (e) => this.updateMaxSegment(e));
+        document.getElementById('blur-slider').addEventListener('input', (e) => this.updateBlur(e));
+        document.getElementById('sharpen-slider').addEventListener('input', (e) => this.updateSharpen(e));
+        document.getElementById('colors-slider').addEventListener('input', (e) => this.updateColors(e));
+        document.getElementById('grain-slider').addEventListener('input', (e) => this.updateGrain(e));
+        document.getElementById('shift-slider').addEventListener('input', (e) => this.updateChannelShift(e));
         document.getElementById('algorithm-btn').addEventListener('click', () => this.cycleAlgorithm());
         document.getElementById('mode-btn').addEventListener('click', () => this.cycleMode());
-        document.getElementById('save-btn').addEventListener('click', () => this.saveImage());
-        document.getElementById('iterate-btn').addEventListener('click', () => this.iterateImage());
+        document.getElementById('order-btn').addEventListener('click', () => this.toggleReverse());
+        document.getElementById('invert-btn').addEventListener('click', () => this.toggleInvertIntervals());
         document.getElementById('new-btn').addEventListener('click', () => this.newImage());
+        document.getElementById('reapply-btn').addEventListener('click', () => this.reapplyLastSettings());
+        document.getElementById('variations-btn').addEventListener('click', () => this.showVariations());
+        document.getElementById('variations').addEventListener('click', (e) => {
+            if (e.target.id === 'variations') this.hideVariations();
+        });
+        document.getElementById('tile-btn').addEventListener('click', () => this.showTilePreview());
+        document.getElementById('tile-preview').addEventListener('click', () => {
+            document.getElementById('tile-preview').classList.remove('show');
+        });
+        document.getElementById('animation-btn').addEventListener('click', () => this.exportAnimation());
+        document.getElementById('contact-btn').addEventListener('click', () => this.exportContactSheet());
+        document.getElementById('compare-btn').addEventListener('click', () => this.exportComparison());
+        document.getElementById('save-settings-btn').addEventListener('click', () => this.exportSettings());
+        document.getElementById('load-settings-btn').addEventListener('click', () => {
+            document.getElementById('settings-input').click();
+        });
+        document.getElementById('settings-input').addEventListener('change', (e) => this.importSettings(e));
+        document.getElementById('layout-btn').addEventListener('click', () => this.cycleComparisonLayout());
+        document.getElementById('format-btn').addEventListener('click', () => this.cycleExportFormat());
+        document.getElementById('format-text').textContent = this.exportFormat.name;
+        document.getElementById('normalize-btn').addEventListener('click', () => this.toggleNormalize());
+        document.getElementById('scale-btn').addEventListener('click', () => this.cycleProcessScale());
+        document.getElementById('macro-btn').addEventListener('click', () => this.cycleMacro());
+        document.getElementById('apply-macro-btn').addEventListener('click', () => this.applyMacro());
+        document.getElementById('save-macro-btn').addEventListener('click', () => this.saveMacro());
+        document.getElementById('print-btn').addEventListener('click', () => this.printImage());
+        document.getElementById('watermark-btn').addEventListener('click', () => this.cycleWatermark());
+        document.getElementById('watermark-slider').addEventListener('input', (e) => this.updateWatermarkOpacity(e));
+        document.getElementById('sidecar-btn').addEventListener('click', () => this.toggleSidecar());
+        document.getElementById('session-btn').addEventListener('click', () => this.renameSession());
+        document.getElementById('session-text').textContent = this.sessionId;
+        document.getElementById('fps-btn').addEventListener('click', () => this.cycleAnimationFps());
+        document.getElementById('sweep-btn').addEventListener('click', () => this.exportThresholdSweep());
+
+        // Clipboard image writes need the async Clipboard API
+        const copyBtn = document.getElementById('copy-btn');
+        if (navigator.clipboard && window.ClipboardItem) {
+            copyBtn.addEventListener('click', () => this.copyToClipboard());
+        } else {
+            copyBtn.style.display = 'none';
+        }
+    }
+
+    initDragAndDrop() {
+        const app = document.getElementById('app');
+
+        document.addEventListener('dragover', (e) => {
+            e.preventDefault();
+            app.classList.add('drag-over');
+        });
+        document.addEventListener('dragleave', (e) => {
+            if (!e.relatedTarget) app.classList.remove('drag-over');
+        });
+        document.addEventListener('drop', (e) => {
+            e.preventDefault();
+            app.classList.remove('drag-over');
+
+            const file = e.dataTransfer.files[0];
+            if (!file) return;
+            if (file.type.startsWith('image/')) {
+                this.loadImageFromFile(file);
+            } else {
+                this.showStatus('Unsupported file type');
+            }
+        });
+    }
+
+    initClipboard() {
+        document.addEventListener('paste', (e) => {
+            const items = e.clipboardData ? Array.from(e.clipboardData.items) : [];
+            const item = items.find(entry => entry.kind === 'file' && entry.type.startsWith('image/'));
+            if (!item) return;
+
+            e.preventDefault();
+            this.loadImageFromFile(item.getAsFile());
+        });
     }
 
     openCamera() {
@@ -139,55 +324,267 @@ class PixelSortApp {
     }
 
     handleFileSelect(event) {
-        const file = event.target.files[0];
+        const [file, ...rest] = Array.from(event.target.files);
         if (file) {
-            this.loadImageFromFile(file);
+            // Extra selections wait in the queue for New Image
+            this.imageQueue = rest;
+            this.updateQueueCount();
+            this.loadImageFromFile(file, true);
+        }
+
+        // Allow picking the same file again
+        event.target.value = '';
+    }
+
+    loadNextQueued() {
+        const file = this.imageQueue.shift();
+        this.updateQueueCount();
+        this.loadImageFromFile(file, true);
+    }
+
+    updateQueueCount() {
+        const count = this.imageQueue.length;
+        document.getElementById('queue-text').textContent = count ? `${count} queued` : '';
+    }
+
+    /**
+     * @param {boolean} keepQueue - True when the file comes from the multi-file queue
+     */
+    loadImageFromFile(file, keepQueue = false) {
+        // Only the most recent pick wins if several decodes overlap
+        const token = ++this.loadToken;
+        document.getElementById('app').classList.add('loading');
+
+        this.decodeImage(file)
+            .then(img => {
+                if (token === this.loadToken) {
+                    this.sourceFile = file;
+                    this.setSourceImage(img, keepQueue);
+                }
+            })
+            .catch(err => {
+                console.error('Image decode failed:', err);
+                if (token === this.loadToken) this.showStatus('Could not read image');
+            })
+            .finally(() => {
+                if (token === this.loadToken) {
+                    document.getElementById('app').classList.remove('loading');
+                }
+            });
+    }
+
+    decodeImage(file) {
+        // Decode with the EXIF orientation applied so phone photos load upright
+        if (window.createImageBitmap) {
+            return createImageBitmap(file, { imageOrientation: 'from-image' })
+                .catch(() => this.decodeWithReader(file));
         }
+        return this.decodeWithReader(file);
+    }
+
+    loadImageFromUrl() {
+        const url = prompt('Image URL');
+        if (!url) return;
+
+        const controller = new AbortController();
+        const timeout = setTimeout(() => controller.abort(), 15000);
+        this.showStatus('Loading image...');
+
+        fetch(url.trim(), { signal: controller.signal })
+            .then(response => {
+                if (!response.ok) throw new Error(`HTTP ${response.status}`);
+                return response.blob();
+            })
+            .then(blob => {
+                if (!blob.type.startsWith('image/')) throw new Error('Not an image');
+                this.loadImageFromFile(blob);
+            })
+            .catch(err => {
+                console.error('URL load failed:', err);
+                if (err.name === 'AbortError') {
+                    this.showStatus('Download timed out');
+                } else if (err.message === 'Not an image') {
+                    this.showStatus('URL is not an image');
+                } else {
+                    this.showStatus('Could not load image');
+                }
+            })
+            .finally(() => clearTimeout(timeout));
     }
 
-    loadImageFromFile(file) {
-        const reader = new FileReader();
-        reader.onload = (e) => {
-            const img = new Image();
-            img.onload = () => {
-                this.originalImage = img;
-                this.processImage();
-                this.switchToEditPhase();
+    decodeWithReader(file) {
+        return new Promise((resolve, reject) => {
+            const reader = new FileReader();
+            reader.onload = (e) => {
+                const img = new Image();
+                img.onload = () => resolve(img);
+                img.onerror = reject;
+                img.src = e.target.result;
             };
-            img.src = e.target.result;
-        };
-        reader.readAsDataURL(file);
+            reader.onerror = reject;
+            reader.readAsDataURL(file);
+        });
+    }
+
+    /**
+     * Start editing a new image; anything loaded outside the queue (drop, paste, camera, URL)
+     * replaces the queue too
+     */
+    setSourceImage(img, keepQueue = false) {
+        if (!keepQueue) {
+            this.imageQueue = [];
+            this.updateQueueCount();
+        }
+
+        img = this.limitImageSize(img);
+        this.originalImage = img;
+        // Iterating replaces originalImage, so keep the loaded source for comparisons
+        this.sourceImage = img;
+        this.iterationCount = 0;
+        this.iterationFrames = [this.createFrame(img)];
+        this.startSession();
+        this.processImage();
+        this.switchToEditPhase();
+    }
+
+    /**
+     * Downscale images larger than maxImageSize so sorting stays responsive
+     */
+    limitImageSize(img) {
+        const longSide = Math.max(img.width, img.height);
+        const shortSide = Math.min(img.width, img.height);
+        const scale = Math.min(1, this.maxImageSize.long / longSide, this.maxImageSize.short / shortSide);
+        if (scale >= 1) return img;
+
+        const canvas = document.createElement('canvas');
+        canvas.width = Math.round(img.width * scale);
+        canvas.height = Math.round(img.height * scale);
+        canvas.getContext('2d').drawImage(img, 0, 0, canvas.width, canvas.height);
+
+        console.info(`Downscaled ${img.width}x${img.height} to ${canvas.width}x${canvas.height}`);
+        return canvas;
     }
 
     processImage() {
         if (!this.originalImage) return;
 
-        const canvas = document.getElementById('display-canvas');
-        const ctx = canvas.getContext('2d', { willReadFrequently: true });
+        this.rememberLastParams();
+
+        const source = this.originalImage;
+        const { width, height } = source;
         
-        // Set canvas size to match image
-        canvas.width = this.originalImage.width;
-        canvas.height = this.originalImage.height;
+        // Sort at reduced resolution when processScale < 1, trading detail for speed
+        const { algorithm, processScale, ...params } = this.currentSettings();
+        const scaled = processScale < 1;
+        const work = document.createElement('canvas');
+        work.width = Math.max(1, Math.round(width * processScale));
+        work.height = Math.max(1, Math.round(height * processScale));
+        const workCtx = work.getContext('2d', { willReadFrequently: true });
         
         // Draw original image
-        ctx.drawImage(this.originalImage, 0, 0);
+        workCtx.drawImage(this.originalImage, 0, 0, work.width, work.height);
         
         // Get image data
-        const imageData = ctx.getImageData(0, 0, canvas.width, canvas.height);
+        const imageData = workCtx.getImageData(0, 0, work.width, work.height);
         
-        // Apply pixel sorting
-        const params = {
-            threshold: this.threshold,
-            hueShift: this.hueShift,
-            sortMode: this.currentMode
+        // Apply pixel sorting; the canvas keeps the previous result until this one is ready
+        this.runSortJob(imageData, algorithm, params, sortedData => {
+            // The image was replaced or closed while sorting
+            if (source !== this.originalImage) return;
+
+            const canvas = document.getElementById('display-canvas');
+            const ctx = canvas.getContext('2d', { willReadFrequently: true });
+            canvas.width = width;
+            canvas.height = height;
+
+            // Nearest-neighbor upscale keeps the blocky sorted look
+            if (scaled) {
+                workCtx.putImageData(sortedData, 0, 0);
+                ctx.imageSmoothingEnabled = false;
+                ctx.drawImage(work, 0, 0, canvas.width, canvas.height);
+            } else {
+                ctx.putImageData(sortedData, 0, 0);
+            }
+
+            this.processedImage = canvas;
+        });
+    }
+
+    /**
+     * Sort in a Web Worker so the page stays responsive; a newer job terminates the running one.
+     * Falls back to sorting on the main thread where workers are unavailable (e.g. file:// pages).
+     */
+    runSortJob(imageData, algorithm, params, done) {
+        const sortHere = () => done(this.sorter.sortPixels(imageData, algorithm, params));
+
+        if (this.sorting && this.sortWorker) {
+            this.sortWorker.terminate();
+            this.sortWorker = null;
+        }
+
+        if (!this.sortWorker && !this.workerUnavailable) {
+            try {
+                this.sortWorker = new Worker('sortworker.js');
+            } catch (error) {
+                this.workerUnavailable = true;
+            }
+        }
+
+        if (!this.sortWorker) {
+            sortHere();
+            return;
+        }
+
+        const worker = this.sortWorker;
+        this.setSorting(true, 0);
+
+        worker.onmessage = (event) => {
+            const message = event.data;
+            if (message.type === 'progress') {
+                this.setSorting(true, message.fraction);
+                return;
+            }
+            this.setSorting(false);
+            done(new ImageData(new Uint8ClampedArray(message.buffer), message.width, message.height));
         };
-        
-        const sortedData = this.sorter.sortPixels(imageData, this.currentAlgorithm, params);
-        
-        // Put sorted data back
-        ctx.putImageData(sortedData, 0, 0);
-        
-        this.processedImage = canvas;
+
+        // Script failed to load or crashed: stop using workers and sort here instead
+        worker.onerror = (event) => {
+            event.preventDefault();
+            worker.terminate();
+            this.sortWorker = null;
+            this.workerUnavailable = true;
+            this.setSorting(false);
+            sortHere();
+        };
+
+        worker.postMessage({
+            width: imageData.width,
+            height: imageData.height,
+            buffer: imageData.data.buffer,
+            algorithm,
+            params
+        });
+    }
+
+    /**
+     * Whether the canvas shows the result of the current settings; actions that read it
+     * wait for a running sort instead of using the previous result
+     */
+    resultReady() {
+        if (!this.processedImage) return false;
+        if (this.sorting) {
+            this.showStatus('Still sorting, try again in a moment');
+            return false;
+        }
+        return true;
+    }
+
+    setSorting(sorting, fraction = 1) {
+        this.sorting = sorting;
+        const container = document.querySelector('#edit-phase .image-container');
+        container.classList.toggle('sorting', sorting);
+        container.style.setProperty('--sort-progress', fraction);
     }
 
     switchToEditPhase() {
@@ -199,8 +596,10 @@ class PixelSortApp {
         document.getElementById('input-phase').style.display = 'flex';
         document.getElementById('edit-phase').style.display = 'none';
         this.originalImage = null;
+        this.sourceImage = null;
         this.processedImage = null;
         this.iterationCount = 0;
+        this.iterationFrames = [];
     }
 
     updateThreshold(event) {
@@ -215,6 +614,72 @@ class PixelSortApp {
         this.processImage();
     }
 
+    updateAngle(event) {
+        this.angle = parseInt(event.target.value);
+        document.getElementById('angle-value').textContent = this.angle;
+        this.processImage();
+    }
+
+    updatePasses(event) {
+        this.passes = parseInt(event.target.value);
+        document.getElementById('passes-value').textContent = this.passes;
+        this.processImage();
+    }
+
+    updateRandomLength(event) {
+        this.randomLength = parseInt(event.target.value);
+        document.getElementById('random-length-value').textContent = this.randomLength;
+        this.processImage();
+    }
+
+    updateMinSegment(event) {
+        this.minSegment = parseInt(event.target.value);
+        document.getElementById('min-segment-value').textContent = this.minSegment > 0 ? this.minSegment : 'Off';
+        this.processImage();
+    }
+
+    updateMaxSegment(event) {
+        this.maxSegment = parseInt(event.target.value);
+        document.getElementById('max-segment-value').textContent = this.maxSegment > 0 ? this.maxSegment : 'Off';
+        this.processImage();
+    }
+
+    updateOpacity(event) {
+        this.opacity = parseFloat(event.target.value);
+        document.getElementById('opacity-value').textContent = this.opacity;
+        this.processImage();
+    }
+
+    updateBlur(event) {
+        this.blurRadius = parseFloat(event.target.value);
+        document.getElementById('blur-value').textContent = this.blurRadius;
+        this.processImage();
+    }
+
+    updateSharpen(event) {
+        this.sharpenAmount = parseFloat(event.target.value);
+        document.getElementById('sharpen-value').textContent = this.sharpenAmount;
+        this.processImage();
+    }
+
+    updateColors(event) {
+        this.paletteColors = parseInt(event.target.value);
+        document.getElementById('colors-value').textContent = this.paletteColors > 1 ? this.paletteColors : 'Off';
+        this.processImage();
+    }
+
+    updateGrain(event) {
+        this.grainIntensity = parseInt(event.target.value);
+        document.getElementById('grain-value').textContent = this.grainIntensity;
+        this.processImage();
+    }
+
+    updateChannelShift(event) {
+        this.channelShift = parseInt(event.target.value);
+        document.getElementById('shift-value').textContent = this.channelShift;
+        this.processImage();
+    }
+
     cycleAlgorithm() {
         const algorithms = this.sorter.algorithms;
         const currentIndex = algorithms.indexOf(this.currentAlgorithm);
@@ -231,52 +696,928 @@ class PixelSortApp {
         this.processImage();
     }
 
-    saveImage() {
-        if (!this.processedImage) return;
+    toggleReverse() {
+        this.reverse = !this.reverse;
+        document.getElementById('order-text').textContent = this.reverse ? 'Descending' : 'Ascending';
+        this.processImage();
+    }
+
+    cycleIntervalMode() {
+        const modes = this.sorter.intervalModes;
+        const currentIndex = modes.indexOf(this.intervalMode);
+        this.intervalMode = modes[(currentIndex + 1) % modes.length];
+        document.getElementById('interval-text').textContent = this.intervalMode;
+        this.processImage();
+    }
+
+    reseedIntervals() {
+        this.randomSeed = Math.floor(Math.random() * 999999) + 1;
+        document.getElementById('seed-text').textContent = this.randomSeed;
+        this.processImage();
+    }
+
+    toggleInvertIntervals() {
+        this.invertIntervals = !this.invertIntervals;
+        document.getElementById('invert-text').textContent = this.invertIntervals ? 'On' : 'Off';
+        this.processImage();
+    }
+
+    toggleNormalize() {
+        this.normalize = !this.normalize;
+        document.getElementById('normalize-text').textContent = this.normalize ? 'On' : 'Off';
+        this.processImage();
+    }
+
+    cycleProcessScale() {
+        const scales = this.processScales;
+        const currentIndex = scales.indexOf(this.processScale);
+        this.processScale = scales[(currentIndex + 1) % scales.length];
+        document.getElementById('scale-text').textContent = this.processScaleLabel();
+        if (this.processScale < 1) {
+            this.showStatus(`Sorting at ${this.processScaleLabel()} size: faster, but blockier`);
+        }
+        this.processImage();
+    }
+
+    processScaleLabel() {
+        return this.processScale === 1 ? 'Full' : `1/${Math.round(1 / this.processScale)}`;
+    }
+
+    cycleMacro() {
+        const currentIndex = this.macros.indexOf(this.currentMacro);
+        this.currentMacro = this.macros[(currentIndex + 1) % this.macros.length];
+        document.getElementById('macro-text').textContent = this.currentMacro.name;
+    }
+
+    /**
+     * Run the selected macro over the current result and iterate on it
+     */
+    applyMacro() {
+        if (!this.resultReady()) return;
 
         const canvas = document.getElementById('display-canvas');
-        const filename = `edit_${String(this.iterationCount).padStart(3, '0')}_${this.currentAlgorithm.toLowerCase()}.png`;
+        const ctx = canvas.getContext('2d', { willReadFrequently: true });
+        const imageData = ctx.getImageData(0, 0, canvas.width, canvas.height);
+        ctx.putImageData(this.sorter.runMacro(imageData, this.currentMacro), 0, 0);
 
-        canvas.toBlob(blob => {
-            const url = URL.createObjectURL(blob);
-            const a = document.createElement('a');
-            a.href = url;
-            a.download = filename;
-            a.click();
-            URL.revokeObjectURL(url);
+        this.iterateImage();
+        this.showStatus(`Applied: ${this.currentMacro.name}`);
+    }
 
-            this.showStatus(`Saved: ${filename}`);
+    /**
+     * Store the current settings chain as a named macro
+     */
+    saveMacro() {
+        const name = prompt('Macro name');
+        if (!name || !name.trim()) return;
+
+        const { algorithm, ...params } = this.currentSettings();
+        const macro = this.sorter.createMacro(name.trim(), algorithm, params);
+        const saved = this.loadSavedMacros().filter(m => m.name !== macro.name);
+        saved.push(macro);
+
+        try {
+            localStorage.setItem('harpy.macros', JSON.stringify(saved));
+        } catch (err) {
+            console.warn('Could not store macro:', err);
+        }
+
+        this.macros = [...PixelSorter.builtInMacros, ...saved];
+        this.currentMacro = this.macros.find(m => m.name === macro.name);
+        document.getElementById('macro-text').textContent = this.currentMacro.name;
+        this.showStatus(`Saved macro: ${macro.name}`);
+    }
+
+    loadSavedMacros() {
+        try {
+            const saved = JSON.parse(localStorage.getItem('harpy.macros'));
+            if (!Array.isArray(saved)) return [];
+            return saved.filter(m => m && typeof m.name === 'string' && Array.isArray(m.steps));
+        } catch (err) {
+            console.warn('Could not read stored macros:', err);
+            return [];
+        }
+    }
+
+    saveImage() {
+        if (!this.resultReady()) return;
+
+        const canvas = this.createExportCanvas();
+        const basename = `edit_${String(this.iterationCount).padStart(3, '0')}_${this.currentAlgorithm.toLowerCase()}`;
+
+        // Every output is encoded from the same canvas
+        const encodes = this.exportFormat.outputs.map(({ type, quality }) =>
+            new Promise(resolve => canvas.toBlob(resolve, type, quality))
+                .then(blob => this.embedSettings(blob))
+                .then(blob => this.embedExif(blob))
+        );
+
+        Promise.all(encodes).then(blobs => {
+            // Browsers without a WebP encoder silently fall back to PNG
+            const filenames = [];
+            for (const blob of blobs) {
+                const filename = `${basename}.${this.extensionFor(blob.type)}`;
+                if (filenames.includes(filename)) continue;
+                this.downloadBlob(blob, filename);
+                filenames.push(filename);
+            }
+
+            if (this.saveSidecar) {
+                const recipe = Recipe.create(this.currentSettings(), {
+                    image: filenames[0],
+                    savedAt: new Date().toISOString()
+                });
+                const sidecar = `${basename}.json`;
+                this.downloadBlob(new Blob([JSON.stringify(recipe, null, 2)], { type: 'application/json' }), sidecar);
+                filenames.push(sidecar);
+            }
+
+            this.rememberSavedSettings(filenames[0]);
+            this.showStatus(`Saved: ${filenames.join(', ')}`);
+        }).catch(err => {
+            console.error('Save failed:', err);
+            this.showStatus('Could not save image');
         });
+
+        this.saveSourceImage();
+    }
+
+    /**
+     * Download the loaded file byte for byte (full size, metadata intact) on the first save
+     * of the session
+     */
+    saveSourceImage() {
+        if (!this.sourceFile || this.sourceSaved) return;
+        this.sourceSaved = true;
+
+        const match = /\.[a-z0-9]+$/i.exec(this.sourceFile.name || '');
+        const extension = match ? match[0].toLowerCase() : `.${this.extensionFor(this.sourceFile.type)}`;
+        this.downloadBlob(this.sourceFile, `${this.sessionId}_original${extension}`);
+    }
+
+    /**
+     * Embed the current settings into PNG output as tEXt chunks
+     */
+    embedSettings(blob) {
+        if (blob.type !== 'image/png') return blob;
+
+        return blob.arrayBuffer().then(buffer => {
+            const png = PngText.embed(new Uint8Array(buffer), {
+                Software: 'Harpy Pixelsort',
+                'pixelsort:settings': JSON.stringify(this.currentSettings())
+            });
+            return new Blob([png], { type: 'image/png' });
+        });
+    }
+
+    /**
+     * Carry the capture timestamps of a JPEG source into JPEG output, best effort
+     */
+    embedExif(blob) {
+        if (blob.type !== 'image/jpeg' || !this.sourceFile) return blob;
+
+        return Promise.all([this.sourceFile.arrayBuffer(), blob.arrayBuffer()])
+            .then(([source, output]) => {
+                // Only timestamps are kept: location and the unsorted thumbnail must not leak
+                const segment = JpegExif.fromSource(new Uint8Array(source));
+                if (!segment) return blob;
+                const jpeg = JpegExif.embed(new Uint8Array(output), segment);
+                return new Blob([jpeg], { type: 'image/jpeg' });
+            })
+            .catch(err => {
+                console.warn('Could not copy EXIF:', err);
+                return blob;
+            });
+    }
+
+    copyToClipboard() {
+        if (!this.resultReady()) return;
+
+        const canvas = document.getElementById('display-canvas');
+        const blob = new Promise(resolve => canvas.toBlob(resolve, 'image/png'));
+
+        navigator.clipboard.write([new ClipboardItem({ 'image/png': blob })])
+            .then(() => this.showStatus('Copied to clipboard'))
+            .catch(err => {
+                console.error('Clipboard write failed:', err);
+                this.showStatus('Could not copy image');
+            });
+    }
+
+    printImage() {
+        if (!this.resultReady()) return;
+
+        // Print styles hide everything except the canvas
+        window.print();
+    }
+
+    /**
+     * Canvas to encode on save; the on-screen preview stays unbranded
+     */
+    createExportCanvas() {
+        const source = document.getElementById('display-canvas');
+        const { position, opacity, scale } = this.watermark;
+        const logo = this.watermarkImage;
+        if (position === 'Off' || !logo.complete || !logo.naturalWidth) return source;
+
+        const canvas = document.createElement('canvas');
+        canvas.width = source.width;
+        canvas.height = source.height;
+        const ctx = canvas.getContext('2d');
+        ctx.drawImage(source, 0, 0);
+
+        const size = Math.round(Math.min(canvas.width, canvas.height) * scale);
+        const margin = Math.round(size * 0.2);
+        const x = position.endsWith('Right') ? canvas.width - size - margin : margin;
+        const y = position.startsWith('Bottom') ? canvas.height - size - margin : margin;
+
+        ctx.globalAlpha = opacity;
+        ctx.drawImage(logo, x, y, size, size);
+
+        // Opened from file:// the logo counts as cross-origin and taints the canvas
+        try {
+            ctx.getImageData(0, 0, 1, 1);
+        } catch (err) {
+            console.warn('Watermark unavailable:', err);
+            this.showStatus('Watermark unavailable here, saving without it');
+            return source;
+        }
+        return canvas;
+    }
+
+    cycleAnimationFps() {
+        const rates = this.animationRates;
+        const currentIndex = rates.indexOf(this.animationFps);
+        this.animationFps = rates[(currentIndex + 1) % rates.length];
+        document.getElementById('fps-text').textContent = `${this.animationFps} fps`;
+    }
+
+    toggleSidecar() {
+        this.saveSidecar = !this.saveSidecar;
+        document.getElementById('sidecar-text').textContent = this.saveSidecar ? 'On' : 'Off';
+    }
+
+    // Only exports are branded, so the preview is not re-sorted
+    updateWatermarkOpacity(event) {
+        this.watermark.opacity = parseFloat(event.target.value);
+        document.getElementById('watermark-value').textContent = this.watermark.opacity;
+    }
+
+    cycleWatermark() {
+        const positions = this.watermarkPositions;
+        const currentIndex = positions.indexOf(this.watermark.position);
+        this.watermark.position = positions[(currentIndex + 1) % positions.length];
+        document.getElementById('watermark-text').textContent = this.watermark.position;
+    }
+
+    extensionFor(mimeType) {
+        switch (mimeType) {
+            case 'image/webp':
+                return 'webp';
+            case 'image/jpeg':
+                return 'jpg';
+            default:
+                return 'png';
+        }
+    }
+
+    cycleExportFormat() {
+        const formats = this.exportFormats;
+        const currentIndex = formats.indexOf(this.exportFormat);
+        this.exportFormat = formats[(currentIndex + 1) % formats.length];
+        document.getElementById('format-text').textContent = this.exportFormat.name;
+
+        try {
+            localStorage.setItem('harpy.exportFormat', this.exportFormat.name);
+        } catch (err) {
+            console.warn('Could not store export format:', err);
+        }
+    }
+
+    /**
+     * Last chosen export format, PNG if none was stored or it no longer exists
+     */
+    loadExportFormat() {
+        let name = null;
+        try {
+            name = localStorage.getItem('harpy.exportFormat');
+        } catch (err) {
+            console.warn('Could not read export format:', err);
+        }
+        return this.exportFormats.find(format => format.name === name) || this.exportFormats[0];
+    }
+
+    /**
+     * Snapshot of the parameters that produce the current image
+     */
+    currentSettings() {
+        return {
+            algorithm: this.currentAlgorithm,
+            threshold: this.threshold,
+            hueShift: this.hueShift,
+            angle: this.angle,
+            opacity: this.opacity,
+            sortMode: this.currentMode,
+            reverse: this.reverse,
+            invertIntervals: this.invertIntervals,
+            intervalMode: this.intervalMode,
+            randomSeed: this.randomSeed,
+            randomLength: this.randomLength,
+            passes: this.passes,
+            minSegment: this.minSegment,
+            maxSegment: this.maxSegment,
+            normalize: this.normalize,
+            blurRadius: this.blurRadius,
+            sharpenAmount: this.sharpenAmount,
+            paletteColors: this.paletteColors,
+            grainIntensity: this.grainIntensity,
+            grainSeed: this.grainSeed,
+            channelShift: this.channelShift,
+            processScale: this.processScale,
+            watermarkOpacity: this.watermark.opacity
+        };
+    }
+
+    /**
+     * Apply a settings object, ignoring missing or invalid fields
+     */
+    applySettings(settings) {
+        if (!settings || typeof settings !== 'object') return false;
+
+        if (this.sorter.algorithms.includes(settings.algorithm)) {
+            this.currentAlgorithm = settings.algorithm;
+        }
+        if (this.sorter.modes.includes(settings.sortMode)) {
+            this.currentMode = settings.sortMode;
+        }
+        if (Number.isFinite(settings.threshold)) {
+            this.threshold = Math.min(255, Math.max(0, Math.round(settings.threshold)));
+        }
+        if (Number.isFinite(settings.hueShift)) {
+            this.hueShift = Math.min(360, Math.max(0, Math.round(settings.hueShift)));
+        }
+        if (Number.isFinite(settings.angle)) {
+            this.angle = Math.min(359, Math.max(0, Math.round(settings.angle)));
+        }
+        if (Number.isFinite(settings.opacity)) {
+            this.opacity = Math.min(1, Math.max(0, settings.opacity));
+        }
+        if (typeof settings.reverse === 'boolean') {
+            this.reverse = settings.reverse;
+        }
+        if (typeof settings.invertIntervals === 'boolean') {
+            this.invertIntervals = settings.invertIntervals;
+        }
+        if (this.sorter.intervalModes.includes(settings.intervalMode)) {
+            this.intervalMode = settings.intervalMode;
+        }
+        if (Number.isInteger(settings.randomSeed)) {
+            this.randomSeed = settings.randomSeed;
+        }
+        if (Number.isFinite(settings.randomLength)) {
+            this.randomLength = Math.min(128, Math.max(2, Math.round(settings.randomLength)));
+        }
+        if (Number.isFinite(settings.passes)) {
+            this.passes = Math.min(10, Math.max(1, Math.round(settings.passes)));
+        }
+        if (Number.isFinite(settings.minSegment)) {
+            this.minSegment = Math.min(64, Math.max(0, Math.round(settings.minSegment)));
+        }
+        if (Number.isFinite(settings.maxSegment)) {
+            this.maxSegment = Math.min(256, Math.max(0, Math.round(settings.maxSegment)));
+        }
+        if (typeof settings.normalize === 'boolean') {
+            this.normalize = settings.normalize;
+        }
+        if (Number.isFinite(settings.blurRadius)) {
+            this.blurRadius = Math.min(10, Math.max(0, settings.blurRadius));
+        }
+        if (Number.isFinite(settings.sharpenAmount)) {
+            this.sharpenAmount = Math.min(3, Math.max(0, settings.sharpenAmount));
+        }
+        if (Number.isFinite(settings.paletteColors)) {
+            this.paletteColors = Math.min(32, Math.max(0, Math.round(settings.paletteColors)));
+        }
+        if (Number.isFinite(settings.grainIntensity)) {
+            this.grainIntensity = Math.min(100, Math.max(0, Math.round(settings.grainIntensity)));
+        }
+        if (Number.isInteger(settings.grainSeed)) {
+            this.grainSeed = settings.grainSeed;
+        }
+        if (Number.isFinite(settings.channelShift)) {
+            this.channelShift = Math.min(20, Math.max(-20, Math.round(settings.channelShift)));
+        }
+        if (this.processScales.includes(settings.processScale)) {
+            this.processScale = settings.processScale;
+        }
+        if (Number.isFinite(settings.watermarkOpacity)) {
+            this.watermark.opacity = Math.min(1, Math.max(0, settings.watermarkOpacity));
+        }
+
+        this.syncControls();
+        this.processImage();
+        return true;
+    }
+
+    /**
+     * Update sliders and button labels to match the current state
+     */
+    syncControls() {
+        document.getElementById('threshold-slider').value = this.threshold;
+        document.getElementById('threshold-value').textContent = this.threshold;
+        document.getElementById('hue-slider').value = this.hueShift;
+        document.getElementById('hue-value').textContent = this.hueShift;
+        document.getElementById('angle-slider').value = this.angle;
+        document.getElementById('angle-value').textContent = this.angle;
+        document.getElementById('opacity-slider').value = this.opacity;
+        document.getElementById('opacity-value').textContent = this.opacity;
+        document.getElementById('algorithm-text').textContent = this.currentAlgorithm;
+        document.getElementById('mode-text').textContent = this.currentMode;
+        document.getElementById('order-text').textContent = this.reverse ? 'Descending' : 'Ascending';
+        document.getElementById('invert-text').textContent = this.invertIntervals ? 'On' : 'Off';
+        document.getElementById('normalize-text').textContent = this.normalize ? 'On' : 'Off';
+        document.getElementById('interval-text').textContent = this.intervalMode;
+        document.getElementById('seed-text').textContent = this.randomSeed;
+        document.getElementById('passes-slider').value = this.passes;
+        document.getElementById('passes-value').textContent = this.passes;
+        document.getElementById('random-length-slider').value = this.randomLength;
+        document.getElementById('random-length-value').textContent = this.randomLength;
+        document.getElementById('min-segment-slider').value = this.minSegment;
+        document.getElementById('min-segment-value').textContent = this.minSegment > 0 ? this.minSegment : 'Off';
+        document.getElementById('max-segment-slider').value = this.maxSegment;
+        document.getElementById('max-segment-value').textContent = this.maxSegment > 0 ? this.maxSegment : 'Off';
+        document.getElementById('blur-slider').value = this.blurRadius;
+        document.getElementById('blur-value').textContent = this.blurRadius;
+        document.getElementById('sharpen-slider').value = this.sharpenAmount;
+        document.getElementById('sharpen-value').textContent = this.sharpenAmount;
+        document.getElementById('colors-slider').value = this.paletteColors;
+        document.getElementById('colors-value').textContent = this.paletteColors > 1 ? this.paletteColors : 'Off';
+        document.getElementById('grain-slider').value = this.grainIntensity;
+        document.getElementById('grain-value').textContent = this.grainIntensity;
+        document.getElementById('shift-slider').value = this.channelShift;
+        document.getElementById('shift-value').textContent = this.channelShift;
+        document.getElementById('scale-text').textContent = this.processScaleLabel();
+        document.getElementById('watermark-slider').value = this.watermark.opacity;
+        document.getElementById('watermark-value').textContent = this.watermark.opacity;
+    }
+
+    /**
+     * Download the current settings as a standalone recipe file
+     */
+    exportSettings() {
+        const recipe = Recipe.create(this.currentSettings());
+        const blob = new Blob([JSON.stringify(recipe, null, 2)], { type: 'application/json' });
+        const filename = `${this.sessionId}_settings.json`;
+        this.downloadBlob(blob, filename);
+        this.showStatus(`Saved: ${filename}`);
+    }
+
+    /**
+     * Apply settings from a recipe file or from a PNG saved by this app
+     */
+    importSettings(event) {
+        const file = event.target.files[0];
+        event.target.value = '';
+        if (!file) return;
+
+        file.arrayBuffer()
+            .then(buffer => {
+                const bytes = new Uint8Array(buffer);
+                if (file.type === 'image/png') {
+                    return JSON.parse(PngText.read(bytes)['pixelsort:settings']);
+                }
+                return Recipe.parse(new TextDecoder().decode(bytes));
+            })
+            .then(settings => {
+                if (!this.applySettings(settings)) throw new Error('No settings found');
+                this.showStatus(`Loaded settings: ${file.name}`);
+            })
+            .catch(err => {
+                console.error('Settings import failed:', err);
+                this.showStatus('Could not read settings');
+            });
+    }
+
+    rememberSavedSettings(filename) {
+        try {
+            localStorage.setItem('harpy.lastSaved', JSON.stringify({
+                filename,
+                settings: this.currentSettings()
+            }));
+        } catch (err) {
+            console.warn('Could not store settings:', err);
+        }
+    }
+
+    /**
+     * Keep the latest settings so the next visit starts where this one left off
+     */
+    rememberLastParams() {
+        try {
+            localStorage.setItem('harpy.lastParams', JSON.stringify(this.currentSettings()));
+        } catch (err) {
+            console.warn('Could not store settings:', err);
+        }
+    }
+
+    restoreLastParams() {
+        try {
+            // applySettings drops unknown or out-of-range values, so a bad entry leaves the defaults
+            this.applySettings(JSON.parse(localStorage.getItem('harpy.lastParams')));
+        } catch (err) {
+            console.warn('Could not read stored settings:', err);
+        }
+    }
+
+    reapplyLastSettings() {
+        let saved = null;
+        try {
+            saved = JSON.parse(localStorage.getItem('harpy.lastSaved'));
+        } catch (err) {
+            console.warn('Could not read stored settings:', err);
+        }
+
+        if (saved && this.applySettings(saved.settings)) {
+            this.showStatus(`Reapplied: ${saved.filename}`);
+        } else {
+            this.showStatus('No saved settings yet');
+        }
+    }
+
+    downloadBlob(blob, filename) {
+        const url = URL.createObjectURL(blob);
+        const a = document.createElement('a');
+        a.href = url;
+        a.download = filename;
+        a.click();
+        URL.revokeObjectURL(url);
     }
 
     iterateImage() {
-        if (!this.processedImage) return;
+        if (!this.resultReady()) return;
 
         this.iterationCount++;
         const canvas = document.getElementById('display-canvas');
+        this.iterationFrames.push(this.createFrame(canvas));
+
+        // Copy synchronously so a sort started right after iterating uses the new input
+        const copy = document.createElement('canvas');
+        copy.width = canvas.width;
+        copy.height = canvas.height;
+        copy.getContext('2d').drawImage(canvas, 0, 0);
+        this.originalImage = copy;
+    }
+
+    /**
+     * Downscaled copy of an image for the session animation
+     */
+    createFrame(source) {
+        const maxSide = 480;
+        const scale = Math.min(1, maxSide / Math.max(source.width, source.height));
+        const frame = document.createElement('canvas');
+        frame.width = Math.max(1, Math.round(source.width * scale));
+        frame.height = Math.max(1, Math.round(source.height * scale));
+        frame.getContext('2d').drawImage(source, 0, 0, frame.width, frame.height);
+        return frame;
+    }
+
+    /**
+     * Export every iteration of the session as an animated GIF
+     */
+    exportAnimation() {
+        if (!this.resultReady()) return;
+
+        const maxFrames = 60;
+        const frames = [...this.iterationFrames, this.createFrame(this.processedImage)];
+
+        // Sample evenly when the session is longer than the cap
+        const step = Math.max(1, frames.length / maxFrames);
+        const selected = [];
+        for (let i = 0; i < frames.length && selected.length < maxFrames; i += step) {
+            selected.push(frames[Math.floor(i)]);
+        }
+        selected[selected.length - 1] = frames[frames.length - 1];
+
+        // Letterbox frames of differing sizes into the largest
+        const width = Math.max(...selected.map(frame => frame.width));
+        const height = Math.max(...selected.map(frame => frame.height));
+        const canvas = document.createElement('canvas');
+        canvas.width = width;
+        canvas.height = height;
+        const ctx = canvas.getContext('2d', { willReadFrequently: true });
+
+        const encoder = new GifEncoder(width, height, 1000 / this.animationFps);
+        for (const frame of selected) {
+            const scale = Math.min(width / frame.width, height / frame.height);
+            const frameWidth = Math.round(frame.width * scale);
+            const frameHeight = Math.round(frame.height * scale);
+            ctx.fillStyle = '#000';
+            ctx.fillRect(0, 0, width, height);
+            ctx.drawImage(frame, Math.floor((width - frameWidth) / 2), Math.floor((height - frameHeight) / 2), frameWidth, frameHeight);
+            encoder.addFrame(ctx.getImageData(0, 0, width, height));
+        }
+
+        const filename = `${this.sessionId}.gif`;
+        this.downloadBlob(encoder.finish(), filename);
+        this.showStatus(`Saved: ${filename}`);
+    }
+
+    /**
+     * Export a GIF sweeping the threshold from 0 to 255 over the current image and settings.
+     * Frames are sorted in their own worker; pressing Sweep again cancels.
+     */
+    exportThresholdSweep() {
+        if (this.sweep) {
+            this.endSweep();
+            this.showStatus('Sweep cancelled');
+            return;
+        }
+        if (!this.originalImage) return;
+
+        const frameCount = 24;
+        const source = this.createFrame(this.originalImage);
+        const { width, height } = source;
+        const imageData = source.getContext('2d').getImageData(0, 0, width, height);
+        const { algorithm, processScale, ...params } = this.currentSettings();
+        const encoder = new GifEncoder(width, height, 1000 / this.animationFps);
+
+        const sweep = { worker: null };
+        if (!this.workerUnavailable) {
+            try {
+                sweep.worker = new Worker('sortworker.js');
+            } catch (error) {
+                this.workerUnavailable = true;
+            }
+        }
+        this.sweep = sweep;
+        document.getElementById('sweep-text').textContent = 'Cancel Sweep';
 
-        const img = new Image();
-        img.onload = () => {
-            this.originalImage = img;
+        const sortFrame = (frameParams) => new Promise((resolve, reject) => {
+            const sortHere = () => {
+                try {
+                    resolve(this.sorter.sortPixels(imageData, algorithm, frameParams));
+                } catch (err) {
+                    reject(err);
+                }
+            };
+            const worker = sweep.worker;
+            if (!worker) {
+                sortHere();
+                return;
+            }
+
+            worker.onmessage = (event) => {
+                const message = event.data;
+                if (message.type !== 'done') return;
+                resolve(new ImageData(new Uint8ClampedArray(message.buffer), message.width, message.height));
+            };
+            // Script failed to load or crashed: finish the sweep on this thread
+            worker.onerror = (event) => {
+                event.preventDefault();
+                worker.terminate();
+                sweep.worker = null;
+                this.workerUnavailable = true;
+                sortHere();
+            };
+            // Copied, not transferred: every frame sorts the same input
+            worker.postMessage({ width, height, buffer: imageData.data.buffer, algorithm, params: frameParams });
+        });
+
+        const renderFrame = (index) => {
+            const threshold = Math.round(index * 255 / (frameCount - 1));
+            this.showStatus(`Rendering sweep ${index + 1}/${frameCount}`);
+
+            return sortFrame({ ...params, threshold }).then(frame => {
+                // Cancelled while this frame was sorting
+                if (this.sweep !== sweep) return;
+
+                encoder.addFrame(frame);
+                if (index + 1 < frameCount) {
+                    // Yield between frames so the page repaints when sorting on this thread
+                    return new Promise(resolve => setTimeout(resolve, 0)).then(() => renderFrame(index + 1));
+                }
+
+                const filename = `${this.sessionId}_sweep.gif`;
+                this.downloadBlob(encoder.finish(), filename);
+                this.showStatus(`Saved: ${filename}`);
+            });
         };
-        img.src = canvas.toDataURL();
+
+        renderFrame(0)
+            .catch(err => {
+                console.error('Sweep export failed:', err);
+                this.showStatus('Could not export sweep');
+            })
+            .finally(() => {
+                // Finished or failed, either way another sweep may start
+                if (this.sweep === sweep) this.endSweep();
+            });
+    }
+
+    endSweep() {
+        if (this.sweep.worker) {
+            this.sweep.worker.terminate();
+        }
+        this.sweep = null;
+        document.getElementById('sweep-text').textContent = 'Sweep';
+    }
+
+    /**
+     * Export every iteration of the session as one labeled grid image
+     */
+    exportContactSheet() {
+        if (!this.resultReady()) return;
+
+        const frames = [...this.iterationFrames, this.createFrame(this.processedImage)];
+        const cols = Math.ceil(Math.sqrt(frames.length));
+        const rows = Math.ceil(frames.length / cols);
+
+        const cellWidth = 240;
+        const cellHeight = Math.round(cellWidth * frames[0].height / frames[0].width);
+        const labelHeight = 24;
+        const gap = 8;
+
+        const canvas = document.createElement('canvas');
+        canvas.width = cols * (cellWidth + gap) + gap;
+        canvas.height = rows * (cellHeight + labelHeight + gap) + gap;
+        const ctx = canvas.getContext('2d');
+        ctx.fillStyle = '#000';
+        ctx.fillRect(0, 0, canvas.width, canvas.height);
+        ctx.font = '14px Arial, sans-serif';
+        ctx.textBaseline = 'middle';
+
+        frames.forEach((frame, i) => {
+            const x = gap + (i % cols) * (cellWidth + gap);
+            const y = gap + Math.floor(i / cols) * (cellHeight + labelHeight + gap);
+            ctx.drawImage(frame, x, y, cellWidth, cellHeight);
+
+            // Frame i was pushed by the i-th iterate, after saving it as edit_(i - 1)
+            const label = i === 0 ? 'original' : `edit_${String(i - 1).padStart(3, '0')}`;
+            ctx.fillStyle = '#fff';
+            ctx.fillText(label, x, y + cellHeight + labelHeight / 2);
+        });
+
+        const filename = `${this.sessionId}_contact_sheet.png`;
+        canvas.toBlob(blob => {
+            this.downloadBlob(blob, filename);
+            this.showStatus(`Saved: ${filename}`);
+        });
+    }
+
+    /**
+     * Export the input and sorted result in one image, separated by a divider
+     */
+    exportComparison() {
+        if (!this.resultReady()) return;
+
+        // Compare against the loaded image; originalImage is replaced on every iteration
+        const before = this.sourceImage;
+        const after = document.getElementById('display-canvas');
+        const divider = Math.max(4, Math.round(Math.min(after.width, after.height) * 0.01));
+        const stacked = this.comparisonLayout === 'Stacked';
+
+        const canvas = document.createElement('canvas');
+        canvas.width = stacked ? after.width : after.width * 2 + divider;
+        canvas.height = stacked ? after.height * 2 + divider : after.height;
+        const ctx = canvas.getContext('2d');
+        ctx.fillStyle = '#fff';
+        ctx.fillRect(0, 0, canvas.width, canvas.height);
+
+        ctx.drawImage(before, 0, 0, after.width, after.height);
+        if (stacked) {
+            ctx.drawImage(after, 0, after.height + divider);
+        } else {
+            ctx.drawImage(after, after.width + divider, 0);
+        }
+
+        const filename = `edit_${String(this.iterationCount).padStart(3, '0')}_comparison.png`;
+        canvas.toBlob(blob => {
+            this.downloadBlob(blob, filename);
+            this.showStatus(`Saved: ${filename}`);
+        });
+    }
+
+    cycleComparisonLayout() {
+        const layouts = this.comparisonLayouts;
+        const currentIndex = layouts.indexOf(this.comparisonLayout);
+        this.comparisonLayout = layouts[(currentIndex + 1) % layouts.length];
+        document.getElementById('layout-text').textContent = this.comparisonLayout;
+    }
+
+    /**
+     * Render the image with a grid of algorithm/threshold combinations;
+     * tapping a tile adopts its settings
+     */
+    showVariations() {
+        if (!this.originalImage) return;
+
+        const thresholds = [20, 60, 120];
+        const source = this.createFrame(this.originalImage);
+        const scale = Math.min(1, 240 / Math.max(source.width, source.height));
+        const width = Math.max(1, Math.round(source.width * scale));
+        const height = Math.max(1, Math.round(source.height * scale));
+
+        const scratch = document.createElement('canvas');
+        scratch.width = width;
+        scratch.height = height;
+        const scratchCtx = scratch.getContext('2d', { willReadFrequently: true });
+        scratchCtx.drawImage(source, 0, 0, width, height);
+        const imageData = scratchCtx.getImageData(0, 0, width, height);
+
+        const grid = document.getElementById('variations-grid');
+        grid.innerHTML = '';
+
+        for (const algorithm of this.sorter.algorithms) {
+            for (const threshold of thresholds) {
+                const settings = { ...this.currentSettings(), algorithm, threshold };
+                const { algorithm: _, ...params } = settings;
+
+                const tile = document.createElement('canvas');
+                tile.width = width;
+                tile.height = height;
+                tile.className = 'variation-tile';
+                tile.title = `${algorithm}, threshold ${threshold}`;
+                tile.getContext('2d').putImageData(this.sorter.sortPixels(imageData, algorithm, params), 0, 0);
+                tile.addEventListener('click', () => {
+                    this.hideVariations();
+                    this.applySettings(settings);
+                });
+                grid.appendChild(tile);
+            }
+        }
+
+        document.getElementById('variations').classList.add('show');
+    }
+
+    hideVariations() {
+        document.getElementById('variations').classList.remove('show');
+    }
+
+    /**
+     * Show the result tiled 2x2 and report how well opposite edges match
+     */
+    showTilePreview() {
+        if (!this.resultReady()) return;
+
+        const source = this.createFrame(this.processedImage);
+        const canvas = document.getElementById('tile-canvas');
+        canvas.width = source.width * 2;
+        canvas.height = source.height * 2;
+        const ctx = canvas.getContext('2d');
+        for (let y = 0; y < 2; y++) {
+            for (let x = 0; x < 2; x++) {
+                ctx.drawImage(source, x * source.width, y * source.height);
+            }
+        }
+
+        const display = document.getElementById('display-canvas');
+        const imageData = display.getContext('2d').getImageData(0, 0, display.width, display.height);
+        const { seamless, horizontalDiff, verticalDiff } = this.sorter.checkSeamless(imageData);
+
+        document.getElementById('tile-preview').classList.add('show');
+        this.showStatus(seamless
+            ? 'Seamless'
+            : `Seams: ${Math.round(horizontalDiff)} horizontal, ${Math.round(verticalDiff)} vertical`);
     }
 
     newImage() {
+        // Keep the current settings when moving through a queue
+        if (this.imageQueue.length &&
+            confirm(`Continue with the next queued image? (${this.imageQueue.length} left)`)) {
+            this.loadNextQueued();
+            return;
+        }
+
         if (confirm('Start over with a new image?')) {
+            this.imageQueue = [];
+            this.updateQueueCount();
+
             // Reset state
             this.threshold = 0;
             this.hueShift = 0;
+            this.angle = 45;
+            this.opacity = 1;
+            this.normalize = false;
+            this.blurRadius = 0;
+            this.sharpenAmount = 0;
+            this.paletteColors = 0;
+            this.grainIntensity = 0;
+            this.channelShift = 0;
+            this.processScale = 1;
             this.currentAlgorithm = 'Horizontal';
             this.currentMode = 'Brightness';
+            this.reverse = false;
+            this.invertIntervals = false;
+            this.intervalMode = 'Threshold';
+            this.randomSeed = 1;
+            this.randomLength = 32;
+            this.passes = 1;
+            this.minSegment = 0;
+            this.maxSegment = 0;
             
             // Reset UI
-            document.getElementById('threshold-slider').value = 0;
-            document.getElementById('threshold-value').textContent = '0';
-            document.getElementById('hue-slider').value = 0;
-            document.getElementById('hue-value').textContent = '0';
-            document.getElementById('algorithm-text').textContent = 'Horizontal';
-            document.getElementById('mode-text').textContent = 'Brightness';
+            this.syncControls();
+            this.rememberLastParams();
             
             // Switch back to input phase
             this.switchToInputPhase();
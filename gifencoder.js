@@ -0,0 +1,159 @@
+// Minimal animated GIF encoder for exporting session reels
+class GifEncoder {
+    /**
+     * @param {number} width - Frame width in pixels
+     * @param {number} height - Frame height in pixels
+     * @param {number} delayMs - Delay between frames in milliseconds
+     */
+    constructor(width, height, delayMs = 500) {
+        this.width = width;
+        this.height = height;
+        this.delay = Math.max(2, Math.round(delayMs / 10));
+        this.bytes = [];
+        this.writeHeader();
+    }
+
+    writeHeader() {
+        this.writeString('GIF89a');
+        this.writeShort(this.width);
+        this.writeShort(this.height);
+        // Global color table, 8 bits per channel, 256 entries
+        this.bytes.push(0xf7, 0, 0);
+
+        // 6x7x6 color cube, padded to 256 entries
+        for (let i = 0; i < 256; i++) {
+            if (i < 252) {
+                const r = Math.floor(i / 42);
+                const g = Math.floor(i / 6) % 7;
+                const b = i % 6;
+                this.bytes.push(Math.round(r * 51), Math.round(g * 42.5), Math.round(b * 51));
+            } else {
+                this.bytes.push(0, 0, 0);
+            }
+        }
+
+        // Netscape extension: loop forever
+        this.bytes.push(0x21, 0xff, 0x0b);
+        this.writeString('NETSCAPE2.0');
+        this.bytes.push(0x03, 0x01, 0x00, 0x00, 0x00);
+    }
+
+    /**
+     * Append a frame; must match the encoder dimensions
+     * @param {ImageData} imageData
+     */
+    addFrame(imageData) {
+        const { data } = imageData;
+        const indices = new Uint8Array(this.width * this.height);
+
+        for (let i = 0; i < indices.length; i++) {
+            const idx = i * 4;
+            const r = Math.round(data[idx] / 51);
+            const g = Math.round(data[idx + 1] / 42.5);
+            const b = Math.round(data[idx + 2] / 51);
+            indices[i] = r * 42 + g * 6 + b;
+        }
+
+        // Graphic control extension with frame delay
+        this.bytes.push(0x21, 0xf9, 0x04, 0x00);
+        this.writeShort(this.delay);
+        this.bytes.push(0x00, 0x00);
+
+        // Image descriptor covering the full canvas
+        this.bytes.push(0x2c);
+        this.writeShort(0);
+        this.writeShort(0);
+        this.writeShort(this.width);
+        this.writeShort(this.height);
+        this.bytes.push(0x00);
+
+        this.writeLZW(indices, 8);
+    }
+
+    /**
+     * Finish the stream
+     * @returns {Blob} - image/gif blob
+     */
+    finish() {
+        this.bytes.push(0x3b);
+        return new Blob([new Uint8Array(this.bytes)], { type: 'image/gif' });
+    }
+
+    /**
+     * LZW-compress color indices into GIF data sub-blocks
+     */
+    writeLZW(indices, minCodeSize) {
+        const clearCode = 1 << minCodeSize;
+        const eoiCode = clearCode + 1;
+        let nextCode = eoiCode + 1;
+        let codeSize = minCodeSize + 1;
+        let table = new Map();
+
+        const output = [];
+        let cur = 0;
+        let curShift = 0;
+        const emit = (code) => {
+            cur |= code << curShift;
+            curShift += codeSize;
+            while (curShift >= 8) {
+                output.push(cur & 0xff);
+                cur >>= 8;
+                curShift -= 8;
+            }
+        };
+
+        emit(clearCode);
+        let prefix = indices[0];
+
+        for (let i = 1; i < indices.length; i++) {
+            const k = indices[i];
+            const key = (prefix << 8) | k;
+            const code = table.get(key);
+
+            if (code !== undefined) {
+                prefix = code;
+                continue;
+            }
+
+            emit(prefix);
+            if (nextCode === 4096) {
+                emit(clearCode);
+                nextCode = eoiCode + 1;
+                codeSize = minCodeSize + 1;
+                table = new Map();
+            } else {
+                if (nextCode >= (1 << codeSize)) codeSize++;
+                table.set(key, nextCode++);
+            }
+            prefix = k;
+        }
+
+        emit(prefix);
+        emit(eoiCode);
+        if (curShift > 0) {
+            output.push(cur & 0xff);
+        }
+
+        this.bytes.push(minCodeSize);
+        for (let i = 0; i < output.length; i += 255) {
+            const block = output.slice(i, i + 255);
+            this.bytes.push(block.length, ...block);
+        }
+        this.bytes.push(0x00);
+    }
+
+    writeShort(value) {
+        this.bytes.push(value & 0xff, (value >> 8) & 0xff);
+    }
+
+    writeString(str) {
+        for (let i = 0; i < str.length; i++) {
+            this.bytes.push(str.charCodeAt(i));
+        }
+    }
+}
+
+// Export for use in app.js
+if (typeof module !== 'undefined' && module.exports) {
+    module.exports = GifEncoder;
+}
@@ -0,0 +1,198 @@
+// JPEG EXIF (APP1) helpers for carrying camera metadata into JPEG exports
+class JpegExif {
+    /**
+     * Find the EXIF APP1 segment of a JPEG
+     * @param {Uint8Array} jpeg - Encoded JPEG bytes
+     * @returns {Uint8Array|null} - The whole segment including its marker, or null
+     */
+    static extract(jpeg) {
+        if (jpeg.length < 4 || jpeg[0] !== 0xff || jpeg[1] !== 0xd8) return null;
+
+        let offset = 2;
+        while (offset + 4 <= jpeg.length && jpeg[offset] === 0xff) {
+            const marker = jpeg[offset + 1];
+            // Image data starts here, no metadata follows
+            if (marker === 0xda || marker === 0xd9) break;
+
+            const length = (jpeg[offset + 2] << 8) | jpeg[offset + 3];
+            if (marker === 0xe1 && JpegExif.hasExifHeader(jpeg, offset + 4)) {
+                return jpeg.slice(offset, offset + 2 + length);
+            }
+            offset += 2 + length;
+        }
+
+        return null;
+    }
+
+    /**
+     * Build a fresh EXIF segment holding only the source's timestamps. GPS, the
+     * thumbnail, dimensions and everything else stay behind, and the orientation
+     * is upright because sources are decoded with their orientation applied.
+     * @param {Uint8Array} jpeg - Encoded source JPEG
+     * @returns {Uint8Array|null} - Segment for embed, or null without usable EXIF
+     */
+    static fromSource(jpeg) {
+        const segment = JpegExif.extract(jpeg);
+        if (!segment) return null;
+
+        let tags;
+        try {
+            tags = JpegExif.parse(segment);
+        } catch (err) {
+            return null;
+        }
+
+        const isDate = value => typeof value === 'string' && /^\d{4}:\d\d:\d\d \d\d:\d\d:\d\d$/.test(value);
+        const dateTime = tags.ifd0[JpegExif.TAGS.DateTime];
+        const original = tags.exif[JpegExif.TAGS.DateTimeOriginal];
+        return JpegExif.create({
+            dateTime: isDate(dateTime) ? dateTime : null,
+            dateTimeOriginal: isDate(original) ? original : null
+        });
+    }
+
+    /**
+     * Read the IFD0 and Exif IFD entries of a segment
+     * @param {Uint8Array} segment - Segment returned by extract
+     * @returns {Object} - { ifd0, exif, gps } tag/value maps and hasThumbnail. Values are
+     *     numbers for single SHORT/LONG entries, strings for ASCII, otherwise null.
+     */
+    static parse(segment) {
+        // Marker (2) + length (2) + "Exif\0\0" (6)
+        const tiff = 10;
+        const view = new DataView(segment.buffer, segment.byteOffset, segment.byteLength);
+        const little = segment[tiff] === 0x49;
+
+        const readIfd = (offset) => {
+            const start = tiff + offset;
+            const count = view.getUint16(start, little);
+            const tags = {};
+            for (let i = 0; i < count; i++) {
+                const entry = start + 2 + i * 12;
+                const type = view.getUint16(entry + 2, little);
+                const length = view.getUint32(entry + 4, little);
+                let value = null;
+                if (type === 3 && length === 1) {
+                    value = view.getUint16(entry + 8, little);
+                } else if (type === 4 && length === 1) {
+                    value = view.getUint32(entry + 8, little);
+                } else if (type === 2) {
+                    const at = length > 4 ? tiff + view.getUint32(entry + 8, little) : entry + 8;
+                    value = String.fromCharCode(...segment.subarray(at, at + length)).replace(/\0+$/, '');
+                }
+                tags[view.getUint16(entry, little)] = value;
+            }
+            return { tags, next: view.getUint32(start + 2 + count * 12, little) };
+        };
+
+        const ifd0 = readIfd(view.getUint32(tiff + 4, little));
+        const exifOffset = ifd0.tags[JpegExif.TAGS.ExifIFD];
+        const gpsOffset = ifd0.tags[JpegExif.TAGS.GPSIFD];
+        return {
+            ifd0: ifd0.tags,
+            exif: exifOffset ? readIfd(exifOffset).tags : {},
+            gps: gpsOffset ? readIfd(gpsOffset).tags : {},
+            hasThumbnail: ifd0.next !== 0
+        };
+    }
+
+    /**
+     * Encode a minimal big-endian EXIF segment
+     * @param {Object} dates - dateTime and dateTimeOriginal as "YYYY:MM:DD HH:MM:SS", or null
+     * @returns {Uint8Array} - Segment for embed
+     */
+    static create({ dateTime = null, dateTimeOriginal = null } = {}) {
+        const { TAGS } = JpegExif;
+        const ifd0Count = 1 + (dateTime ? 1 : 0) + (dateTimeOriginal ? 1 : 0);
+        const ifd0Size = 2 + ifd0Count * 12 + 4;
+        const exifSize = dateTimeOriginal ? 2 + 12 + 4 : 0;
+        // Date strings are 19 characters plus the terminating NUL
+        const dataStart = 8 + ifd0Size + exifSize;
+        const tiffSize = dataStart + (dateTime ? 20 : 0) + (dateTimeOriginal ? 20 : 0);
+
+        const segment = new Uint8Array(10 + tiffSize);
+        const view = new DataView(segment.buffer);
+        view.setUint16(0, 0xffe1);
+        view.setUint16(2, segment.length - 2);
+        segment.set([0x45, 0x78, 0x69, 0x66, 0, 0], 4);
+
+        const tiff = 10;
+        segment.set([0x4d, 0x4d], tiff);
+        view.setUint16(tiff + 2, 42);
+        view.setUint32(tiff + 4, 8);
+
+        let data = dataStart;
+        const writeEntry = (at, tag, type, count, value) => {
+            view.setUint16(tiff + at, tag);
+            view.setUint16(tiff + at + 2, type);
+            view.setUint32(tiff + at + 4, count);
+            if (type === 3) {
+                view.setUint16(tiff + at + 8, value);
+            } else {
+                view.setUint32(tiff + at + 8, value);
+            }
+        };
+        const writeDate = (at, tag, text) => {
+            writeEntry(at, tag, 2, 20, data);
+            for (let i = 0; i < text.length; i++) {
+                segment[tiff + data + i] = text.charCodeAt(i);
+            }
+            data += 20;
+        };
+
+        // IFD0 entries must be sorted by tag
+        let entry = 8 + 2;
+        view.setUint16(tiff + 8, ifd0Count);
+        writeEntry(entry, TAGS.Orientation, 3, 1, 1);
+        entry += 12;
+        if (dateTime) {
+            writeDate(entry, TAGS.DateTime, dateTime);
+            entry += 12;
+        }
+        if (dateTimeOriginal) {
+            const exifIfd = 8 + ifd0Size;
+            writeEntry(entry, TAGS.ExifIFD, 4, 1, exifIfd);
+            view.setUint16(tiff + exifIfd, 1);
+            writeDate(exifIfd + 2, TAGS.DateTimeOriginal, dateTimeOriginal);
+        }
+
+        return segment;
+    }
+
+    /**
+     * Insert an APP1 segment after the SOI marker (and JFIF header, if any)
+     * @param {Uint8Array} jpeg - Encoded JPEG bytes without EXIF
+     * @param {Uint8Array} segment - Segment returned by create or fromSource
+     * @returns {Uint8Array} - JPEG bytes with the segment added
+     */
+    static embed(jpeg, segment) {
+        let insertAt = 2;
+        if (jpeg[2] === 0xff && jpeg[3] === 0xe0) {
+            insertAt += 2 + ((jpeg[4] << 8) | jpeg[5]);
+        }
+
+        const result = new Uint8Array(jpeg.length + segment.length);
+        result.set(jpeg.subarray(0, insertAt), 0);
+        result.set(segment, insertAt);
+        result.set(jpeg.subarray(insertAt), insertAt + segment.length);
+        return result;
+    }
+
+    static hasExifHeader(bytes, offset) {
+        return String.fromCharCode(...bytes.subarray(offset, offset + 4)) === 'Exif' &&
+            bytes[offset + 4] === 0 && bytes[offset + 5] === 0;
+    }
+}
+
+JpegExif.TAGS = {
+    Orientation: 0x0112,
+    DateTime: 0x0132,
+    ExifIFD: 0x8769,
+    GPSIFD: 0x8825,
+    DateTimeOriginal: 0x9003
+};
+
+// Export for use in app.js
+if (typeof module !== 'undefined' && module.exports) {
+    module.exports = JpegExif;
+}
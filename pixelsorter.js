@@ -1,198 +1,457 @@
 // Pixel Sorter Engine - Ported from Rust
 class PixelSorter {
     constructor() {
-        this.algorithms = ['Horizontal', 'Vertical', 'Diagonal'];
-        this.modes = ['Brightness', 'Black', 'White'];
+        this.algorithms = [];
+        this.algorithmHandlers = {};
+        this.modes = [];
+        this.sortKeys = {};
+        this.intervalModes = ['Threshold', 'Edges', 'Random'];
+        this.effects = [];
+
+        // Built-in sort algorithms, in cycle order
+        this.registerAlgorithm('Horizontal', (img, p) => this.sortHorizontal(img, p));
+        this.registerAlgorithm('Vertical', (img, p) => this.sortVertical(img, p));
+        this.registerAlgorithm('Diagonal', (img, p) => this.sortDiagonal(img, p));
+        this.registerAlgorithm('Radial', (img, p) => this.sortRadial(img, p));
+
+        // Built-in sort keys, in cycle order
+        this.registerSortKey('Brightness', pixel => this.pixelBrightness(pixel));
+        this.registerSortKey('Black', pixel => pixel.r);
+        this.registerSortKey('White', pixel => 255 - pixel.r);
+        this.registerSortKey('Hue', pixel => this.rgbToHsv(pixel).h / 360 * 255, { circular: true });
+        this.registerSortKey('Saturation', pixel => this.rgbToHsv(pixel).s * 255);
+        this.registerSortKey('Green', pixel => pixel.g);
+        this.registerSortKey('Blue', pixel => pixel.b);
+
+        // Built-in effects; 'pre' runs before sorting, 'post' after, in registration order
+        this.registerEffect({
+            name: 'Hue Shift',
+            stage: 'pre',
+            isActive: p => p.hueShift !== 0,
+            apply: (img, p) => this.applyHueShift(img, p.hueShift)
+        });
+        this.registerEffect({
+            name: 'Normalize',
+            stage: 'pre',
+            isActive: p => p.normalize,
+            apply: img => this.normalize(img)
+        });
+        this.registerEffect({
+            name: 'Blur',
+            stage: 'pre',
+            isActive: p => p.blurRadius > 0,
+            apply: (img, p) => this.gaussianBlur(img, p.blurRadius)
+        });
+        this.registerEffect({
+            name: 'Sharpen',
+            stage: 'pre',
+            isActive: p => p.sharpenAmount > 0,
+            apply: (img, p) => this.sharpen(img, p.sharpenAmount)
+        });
+        this.registerEffect({
+            name: 'Palette',
+            stage: 'post',
+            isActive: p => p.paletteColors > 1,
+            apply: (img, p) => this.quantize(img, p.paletteColors)
+        });
+        this.registerEffect({
+            name: 'Grain',
+            stage: 'post',
+            isActive: p => p.grainIntensity > 0,
+            apply: (img, p) => this.addGrain(img, p.grainIntensity, p.grainSeed)
+        });
+        this.registerEffect({
+            name: 'Channel Shift',
+            stage: 'post',
+            isActive: p => p.channelShift !== 0,
+            apply: (img, p) => this.shiftChannels(img, p.channelShift)
+        });
+        this.registerEffect({
+            name: 'Opacity',
+            stage: 'post',
+            isActive: p => p.opacity < 1,
+            apply: (img, p, original) => this.blend(img, original, p.opacity)
+        });
+    }
+
+    /**
+     * Add a sort algorithm to the cycle
+     * @param {string} name - Display name
+     * @param {Function} sort - (imageData, params) => void, sorts in place
+     */
+    registerAlgorithm(name, sort) {
+        if (!this.algorithmHandlers[name]) {
+            this.algorithms.push(name);
+        }
+        this.algorithmHandlers[name] = sort;
+    }
+
+    /**
+     * Add a sort key to the mode cycle
+     * @param {string} name - Display name
+     * @param {Function} key - ({ r, g, b, a }) => number, scaled to 0-255 so one threshold fits every key
+     * @param {Object} options - circular: the key wraps around (like hue), so 0 and 255 are neighbours
+     */
+    registerSortKey(name, key, { circular = false } = {}) {
+        if (!this.sortKeys[name]) {
+            this.modes.push(name);
+        }
+        this.sortKeys[name] = { key, circular };
+    }
+
+    /**
+     * Add an effect to the processing chain
+     * @param {Object} effect - { name, stage: 'pre'|'post', isActive(params), apply(imageData, params, original) }
+     *   where original is the unprocessed input of the whole chain
+     */
+    registerEffect(effect) {
+        this.effects.push(effect);
     }
 
     /**
      * Main sorting function
      * @param {ImageData} imageData - Canvas ImageData
-     * @param {string} algorithm - 'Horizontal', 'Vertical', or 'Diagonal'
-     * @param {Object} params - { threshold, hueShift, sortMode }
+     * @param {string} algorithm - Name of a registered algorithm
+     * @param {Object} params - Overrides for PixelSorter.defaultParams
      * @returns {ImageData} - Sorted ImageData
      */
     sortPixels(imageData, algorithm, params) {
-        const { threshold = 0, hueShift = 0, sortMode = 'Brightness' } = params;
-        const result = new ImageData(
+        return this.sortPixelsWithProgress(imageData, algorithm, params, () => true);
+    }
+
+    /**
+     * sortPixels that reports progress while sorting and can be cancelled
+     * @param {Function} progress - Called with the completed fraction (0-1); return false to abort
+     * @returns {ImageData} - Sorted ImageData, or an unchanged copy of the input when aborted
+     */
+    sortPixelsWithProgress(imageData, algorithm, params, progress) {
+        const settings = { ...PixelSorter.defaultParams, ...params };
+        const copy = () => new ImageData(
             new Uint8ClampedArray(imageData.data),
             imageData.width,
             imageData.height
         );
+        const result = copy();
 
-        // Apply hue shift first if needed
-        if (hueShift !== 0) {
-            this.applyHueShift(result, hueShift);
-        }
+        // Count sorted pixels and report roughly every percent
+        const passes = Math.max(1, Math.floor(settings.passes) || 0);
+        const total = Math.max(1, imageData.width * imageData.height * passes);
+        const step = Math.max(1, Math.floor(total / 100));
+        let done = 0;
+        let nextReport = step;
+        const tracker = {
+            aborted: progress(0) === false,
+            advance: count => {
+                done += count;
+                if (done >= nextReport) {
+                    nextReport = done + step;
+                    tracker.aborted = progress(Math.min(1, done / total)) === false;
+                }
+            }
+        };
+        if (tracker.aborted) return result;
+
+        // Preprocessing changes where interval breaks land
+        this.applyEffects(result, 'pre', settings, imageData);
 
         // Apply sorting based on algorithm
-        switch (algorithm) {
-            case 'Horizontal':
-                this.sortHorizontal(result, threshold, sortMode);
-                break;
-            case 'Vertical':
-                this.sortVertical(result, threshold, sortMode);
-                break;
-            case 'Diagonal':
-                this.sortDiagonal(result, threshold, sortMode);
-                break;
+        this.runSort(result, algorithm, { ...settings, tracker });
+        if (tracker.aborted) return copy();
+
+        this.applyEffects(result, 'post', settings, imageData);
+        progress(1);
+
+        return result;
+    }
+
+    /**
+     * Run a macro's steps in order over a copy of the image
+     * @param {Object} macro - { name, steps: [{ sort: name, params } | { effect: name, params }] }
+     * @returns {ImageData} - Processed ImageData
+     */
+    runMacro(imageData, macro) {
+        const result = new ImageData(
+            new Uint8ClampedArray(imageData.data),
+            imageData.width,
+            imageData.height
+        );
+
+        for (const step of macro.steps) {
+            const params = { ...PixelSorter.defaultParams, ...step.params };
+            if (step.sort) {
+                this.runSort(result, step.sort, params);
+            } else if (step.effect) {
+                const effect = this.effects.find(e => e.name === step.effect);
+                if (effect) effect.apply(result, params, imageData);
+            }
         }
 
         return result;
     }
 
+    /**
+     * Describe what sortPixels would do with these settings as a macro
+     */
+    createMacro(name, algorithm, params) {
+        const settings = { ...PixelSorter.defaultParams, ...params };
+        const active = stage => this.effects
+            .filter(effect => effect.stage === stage && effect.isActive(settings))
+            .map(effect => ({ effect: effect.name, params }));
+
+        return {
+            name,
+            steps: [...active('pre'), { sort: algorithm, params }, ...active('post')]
+        };
+    }
+
+    /**
+     * Run a registered algorithm in place params.passes times; each pass re-finds intervals
+     * in the previous result
+     */
+    runSort(imageData, algorithm, params) {
+        const sort = this.algorithmHandlers[algorithm];
+        if (!sort) return;
+
+        const sortParams = this.prepareSortParams(imageData, params);
+        const passes = Math.max(0, Math.floor(params.passes) || 0);
+        for (let pass = 0; pass < passes; pass++) {
+            sort(imageData, sortParams);
+        }
+    }
+
+    /**
+     * Attach the state an interval mode shares across lines: a Sobel edge map so every line
+     * honors the same edges, or one seeded generator so random cuts are reproducible
+     */
+    prepareSortParams(imageData, params) {
+        switch (params.intervalMode) {
+            case 'Edges':
+                return { ...params, edgeMap: this.detectEdges(imageData) };
+            case 'Random':
+                return { ...params, random: this.createRng(params.randomSeed) };
+            default:
+                return params;
+        }
+    }
+
+    applyEffects(imageData, stage, params, original) {
+        for (const effect of this.effects) {
+            if (effect.stage === stage && effect.isActive(params)) {
+                effect.apply(imageData, params, original);
+            }
+        }
+    }
+
     /**
      * Sort pixels horizontally row by row
      */
-    sortHorizontal(imageData, threshold, sortMode) {
-        const { width, height, data } = imageData;
+    sortHorizontal(imageData, params) {
+        const { width, height } = imageData;
         
         for (let y = 0; y < height; y++) {
-            const rowPixels = [];
+            const line = [];
             for (let x = 0; x < width; x++) {
-                const idx = (y * width + x) * 4;
-                rowPixels.push({
-                    r: data[idx],
-                    g: data[idx + 1],
-                    b: data[idx + 2],
-                    a: data[idx + 3]
-                });
-            }
-
-            const intervals = this.findIntervals(rowPixels, threshold);
-            
-            for (const [start, end] of intervals) {
-                if (end - start > 1) {
-                    const segment = rowPixels.slice(start, end);
-                    segment.sort((a, b) => this.sortKey(a, sortMode) - this.sortKey(b, sortMode));
-                    
-                    for (let i = 0; i < segment.length; i++) {
-                        const x = start + i;
-                        const idx = (y * width + x) * 4;
-                        data[idx] = segment[i].r;
-                        data[idx + 1] = segment[i].g;
-                        data[idx + 2] = segment[i].b;
-                        data[idx + 3] = segment[i].a;
-                    }
-                }
+                line.push(y * width + x);
             }
+            this.sortPixelLine(imageData, line, params);
         }
     }
 
     /**
      * Sort pixels vertically column by column
      */
-    sortVertical(imageData, threshold, sortMode) {
-        const { width, height, data } = imageData;
+    sortVertical(imageData, params) {
+        const { width, height } = imageData;
         
         for (let x = 0; x < width; x++) {
-            const colPixels = [];
+            const line = [];
             for (let y = 0; y < height; y++) {
-                const idx = (y * width + x) * 4;
-                colPixels.push({
-                    r: data[idx],
-                    g: data[idx + 1],
-                    b: data[idx + 2],
-                    a: data[idx + 3]
-                });
+                line.push(y * width + x);
             }
+            this.sortPixelLine(imageData, line, params);
+        }
+    }
 
-            const intervals = this.findIntervals(colPixels, threshold);
-            
-            for (const [start, end] of intervals) {
-                if (end - start > 1) {
-                    const segment = colPixels.slice(start, end);
-                    segment.sort((a, b) => this.sortKey(a, sortMode) - this.sortKey(b, sortMode));
-                    
-                    for (let i = 0; i < segment.length; i++) {
-                        const y = start + i;
-                        const idx = (y * width + x) * 4;
-                        data[idx] = segment[i].r;
-                        data[idx + 1] = segment[i].g;
-                        data[idx + 2] = segment[i].b;
-                        data[idx + 3] = segment[i].a;
-                    }
-                }
+    /**
+     * Sort pixels along parallel lines at params.angle degrees (0 = left to right, 90 = top to bottom)
+     */
+    sortDiagonal(imageData, params) {
+        const { width, height } = imageData;
+        const angle = ((params.angle % 360) + 360) % 360 * Math.PI / 180;
+        const dx = Math.cos(angle);
+        const dy = Math.sin(angle);
+
+        // Step one pixel along the dominant axis so every line has one pixel per column (or row);
+        // lines are keyed by their intercept, which puts each pixel on exactly one line
+        const mostlyHorizontal = Math.abs(dx) >= Math.abs(dy);
+        const slope = mostlyHorizontal ? dy / dx : dx / dy;
+        const lines = new Map();
+        const outer = mostlyHorizontal ? width : height;
+        const inner = mostlyHorizontal ? height : width;
+
+        for (let u = 0; u < outer; u++) {
+            for (let v = 0; v < inner; v++) {
+                const key = Math.round(v - u * slope);
+                if (!lines.has(key)) lines.set(key, []);
+                lines.get(key).push(mostlyHorizontal ? v * width + u : u * width + v);
             }
         }
+
+        const backwards = mostlyHorizontal ? dx < 0 : dy < 0;
+
+        for (const line of lines.values()) {
+            if (backwards) line.reverse();
+            this.sortPixelLine(imageData, line, params);
+        }
     }
 
     /**
-     * Sort pixels diagonally
+     * Sort pixels along rays from the image center out to every edge pixel
      */
-    sortDiagonal(imageData, threshold, sortMode) {
-        const { width, height, data } = imageData;
-        
-        // Process all diagonals (top-left to bottom-right)
-        for (let offset = -height; offset < width; offset++) {
-            const diagonalPixels = [];
-            const positions = [];
-            
-            if (offset >= 0) {
-                for (let i = 0; i < Math.min(height, width - offset); i++) {
-                    const x = i + offset;
-                    const y = i;
-                    const idx = (y * width + x) * 4;
-                    diagonalPixels.push({
-                        r: data[idx],
-                        g: data[idx + 1],
-                        b: data[idx + 2],
-                        a: data[idx + 3]
-                    });
-                    positions.push({ x, y });
-                }
-            } else {
-                for (let i = 0; i < Math.min(width, height + offset); i++) {
-                    const x = i;
-                    const y = i - offset;
-                    const idx = (y * width + x) * 4;
-                    diagonalPixels.push({
-                        r: data[idx],
-                        g: data[idx + 1],
-                        b: data[idx + 2],
-                        a: data[idx + 3]
-                    });
-                    positions.push({ x, y });
-                }
+    sortRadial(imageData, params) {
+        const { width, height } = imageData;
+        const cx = Math.floor(width / 2);
+        const cy = Math.floor(height / 2);
+        const visited = new Uint8Array(width * height);
+
+        // One ray per edge pixel, clockwise from the top-left corner
+        const targets = [];
+        for (let x = 0; x < width; x++) targets.push({ x, y: 0 });
+        for (let y = 1; y < height; y++) targets.push({ x: width - 1, y });
+        for (let x = width - 2; x >= 0; x--) targets.push({ x, y: height - 1 });
+        for (let y = height - 2; y > 0; y--) targets.push({ x: 0, y });
+
+        for (const target of targets) {
+            // Rays overlap near the center; each pixel belongs to the first ray that reaches it
+            const line = this.traceLine(cx, cy, target.x, target.y)
+                .map(({ x, y }) => y * width + x)
+                .filter(index => !visited[index]);
+
+            for (const index of line) {
+                visited[index] = 1;
             }
+            this.sortPixelLine(imageData, line, params);
+        }
+    }
 
-            if (diagonalPixels.length <= 1) continue;
-
-            const intervals = this.findIntervals(diagonalPixels, threshold);
-            
-            for (const [start, end] of intervals) {
-                if (end - start > 1) {
-                    const segment = diagonalPixels.slice(start, end);
-                    segment.sort((a, b) => this.sortKey(a, sortMode) - this.sortKey(b, sortMode));
-                    
-                    for (let i = 0; i < segment.length; i++) {
-                        const { x, y } = positions[start + i];
-                        const idx = (y * width + x) * 4;
-                        data[idx] = segment[i].r;
-                        data[idx + 1] = segment[i].g;
-                        data[idx + 2] = segment[i].b;
-                        data[idx + 3] = segment[i].a;
-                    }
-                }
+    /**
+     * Bresenham line between two pixels, both ends included
+     * @returns {Array} - { x, y } positions from start to end
+     */
+    traceLine(x0, y0, x1, y1) {
+        const positions = [];
+        const dx = Math.abs(x1 - x0);
+        const dy = -Math.abs(y1 - y0);
+        const sx = x0 < x1 ? 1 : -1;
+        const sy = y0 < y1 ? 1 : -1;
+        let err = dx + dy;
+        let x = x0;
+        let y = y0;
+
+        while (true) {
+            positions.push({ x, y });
+            if (x === x1 && y === y1) break;
+            const e2 = 2 * err;
+            if (e2 >= dy) {
+                err += dy;
+                x += sx;
+            }
+            if (e2 <= dx) {
+                err += dx;
+                y += sy;
+            }
+        }
+
+        return positions;
+    }
+
+    /**
+     * Read the pixels at the given indices as one line, sort it and write it back
+     * @param {Array} line - Pixel indices (y * width + x) in line order
+     */
+    sortPixelLine(imageData, line, params) {
+        const { tracker } = params;
+        if (tracker) {
+            if (tracker.aborted) return;
+            tracker.advance(line.length);
+        }
+        if (line.length <= 1) return;
+
+        const { data } = imageData;
+        const pixels = line.map(index => {
+            const idx = index * 4;
+            return {
+                r: data[idx],
+                g: data[idx + 1],
+                b: data[idx + 2],
+                a: data[idx + 3]
+            };
+        });
+
+        this.sortLine(pixels, params, line);
+
+        for (let i = 0; i < pixels.length; i++) {
+            const idx = line[i] * 4;
+            data[idx] = pixels[i].r;
+            data[idx + 1] = pixels[i].g;
+            data[idx + 2] = pixels[i].b;
+            data[idx + 3] = pixels[i].a;
+        }
+    }
+
+    /**
+     * Sort each interval of a line of pixels in place
+     * @param {Array} pixels - { r, g, b, a } objects along the line
+     * @param {Object} params - Sort settings, see PixelSorter.defaultParams
+     * @param {Array} line - Pixel indices of the line, for edge map lookups
+     */
+    sortLine(pixels, params, line) {
+        const { sortMode, reverse } = params;
+        const direction = reverse ? -1 : 1;
+
+        // Compute each key once; comparisons would otherwise redo the color math O(n log n) times
+        const keys = pixels.map(pixel => this.sortKey(pixel, sortMode));
+        const intervals = this.limitIntervals(this.findIntervals(pixels, params, line, keys), params);
+
+        for (const [start, end] of intervals) {
+            const order = [];
+            for (let i = start; i < end; i++) {
+                order.push(i);
+            }
+            order.sort((a, b) => direction * (keys[a] - keys[b]));
+
+            const segment = order.map(i => pixels[i]);
+            for (let i = 0; i < segment.length; i++) {
+                pixels[start + i] = segment[i];
             }
         }
     }
 
     /**
-     * Find intervals in pixel array based on brightness threshold
+     * Find intervals in pixel array where neighboring sort keys differ by at most the threshold,
+     * or by more than it when params.invertIntervals is set. In 'Edges' interval mode the
+     * edge strength under each pixel (looked up through line) replaces the key difference;
+     * 'Random' mode ignores the pixels and cuts at random positions.
+     * @param {Array} keys - Precomputed sort keys of pixels, if the caller has them
+     * @returns {Array} - [start, end) index pairs, each at least two pixels long
      */
-    findIntervals(pixels, threshold) {
+    findIntervals(pixels, params, line, keys = pixels.map(pixel => this.sortKey(pixel, params.sortMode))) {
+        const { threshold, sortMode, invertIntervals, edgeMap } = params;
         if (pixels.length <= 1) return [];
+        if (params.intervalMode === 'Random' && params.random) {
+            return this.randomIntervals(pixels.length, params.randomLength, params.random);
+        }
 
+        const useEdges = params.intervalMode === 'Edges' && edgeMap && line;
         const intervals = [];
         let start = 0;
 
         for (let i = 1; i < pixels.length; i++) {
-            const brightnessDiff = Math.abs(
-                this.pixelBrightness(pixels[i]) - this.pixelBrightness(pixels[i - 1])
-            );
+            const keyDiff = useEdges
+                ? edgeMap[line[i]]
+                : this.keyDistance(keys[i], keys[i - 1], sortMode);
 
-            if (brightnessDiff > threshold) {
+            if (invertIntervals ? keyDiff <= threshold : keyDiff > threshold) {
                 if (i - start > 1) {
                     intervals.push([start, i]);
                 }
@@ -208,6 +467,88 @@ class PixelSorter {
         return intervals;
     }
 
+    /**
+     * Cut a line of the given length into intervals averaging averageLength pixels
+     * @param {Function} random - Generator from createRng
+     */
+    randomIntervals(length, averageLength, random) {
+        const intervals = [];
+        const maxLength = Math.max(2, 2 * averageLength - 1);
+        let start = 0;
+
+        while (start < length) {
+            const end = Math.min(length, start + 1 + Math.floor(random() * maxLength));
+            if (end - start > 1) {
+                intervals.push([start, end]);
+            }
+            start = end;
+        }
+
+        return intervals;
+    }
+
+    /**
+     * Drop intervals shorter than params.minSegment and split ones longer than params.maxSegment
+     * (0 means no limit) into chunks
+     */
+    limitIntervals(intervals, params) {
+        const { minSegment, maxSegment } = params;
+        const limited = [];
+
+        for (const [start, end] of intervals) {
+            if (end - start < minSegment) continue;
+
+            const step = maxSegment > 0 ? maxSegment : end - start;
+            for (let chunkStart = start; chunkStart < end; chunkStart += step) {
+                const chunkEnd = Math.min(end, chunkStart + step);
+                if (chunkEnd - chunkStart > 1) {
+                    limited.push([chunkStart, chunkEnd]);
+                }
+            }
+        }
+
+        return limited;
+    }
+
+    /**
+     * Difference between two sort keys; circular keys such as hue wrap around
+     */
+    keyDistance(a, b, mode) {
+        const diff = Math.abs(a - b);
+        const sortKey = this.sortKeys[mode];
+        return sortKey && sortKey.circular ? Math.min(diff, 255 - diff) : diff;
+    }
+
+    /**
+     * Sobel gradient magnitude of the brightness, scaled to 0-255 like the sort keys
+     * @returns {Float32Array} - One value per pixel
+     */
+    detectEdges(imageData) {
+        const { width, height, data } = imageData;
+        const brightness = new Float32Array(width * height);
+        for (let i = 0; i < brightness.length; i++) {
+            brightness[i] = 0.299 * data[i * 4] + 0.587 * data[i * 4 + 1] + 0.114 * data[i * 4 + 2];
+        }
+
+        // Clamp to the border so edge pixels use their own values
+        const at = (x, y) => brightness[
+            Math.min(height - 1, Math.max(0, y)) * width + Math.min(width - 1, Math.max(0, x))
+        ];
+
+        const edges = new Float32Array(width * height);
+        for (let y = 0; y < height; y++) {
+            for (let x = 0; x < width; x++) {
+                const gx = at(x + 1, y - 1) + 2 * at(x + 1, y) + at(x + 1, y + 1)
+                    - at(x - 1, y - 1) - 2 * at(x - 1, y) - at(x - 1, y + 1);
+                const gy = at(x - 1, y + 1) + 2 * at(x, y + 1) + at(x + 1, y + 1)
+                    - at(x - 1, y - 1) - 2 * at(x, y - 1) - at(x + 1, y - 1);
+                edges[y * width + x] = Math.min(255, Math.hypot(gx, gy) / 4);
+            }
+        }
+
+        return edges;
+    }
+
     /**
      * Calculate pixel brightness using standard RGB to grayscale conversion
      */
@@ -216,21 +557,287 @@ class PixelSorter {
     }
 
     /**
-     * Get sort key based on sort mode
+     * Get the registered sort key of a pixel; unknown modes sort by brightness
      */
     sortKey(pixel, mode) {
-        switch (mode) {
-            case 'Brightness':
-                return this.pixelBrightness(pixel);
-            case 'Black':
-                return pixel.r;
-            case 'White':
-                return 255 - pixel.r;
-            default:
-                return this.pixelBrightness(pixel);
+        const sortKey = this.sortKeys[mode] || this.sortKeys.Brightness;
+        return sortKey.key(pixel);
+    }
+
+    /**
+     * Compare opposite edges to judge whether the image tiles without seams
+     * @returns {Object} - { seamless, horizontalDiff, verticalDiff } with mean channel differences
+     */
+    checkSeamless(imageData, tolerance = 12) {
+        const { width, height, data } = imageData;
+        const diff = (a, b) =>
+            (Math.abs(data[a] - data[b]) + Math.abs(data[a + 1] - data[b + 1]) + Math.abs(data[a + 2] - data[b + 2])) / 3;
+
+        let horizontal = 0;
+        for (let y = 0; y < height; y++) {
+            horizontal += diff(y * width * 4, (y * width + width - 1) * 4);
+        }
+
+        let vertical = 0;
+        for (let x = 0; x < width; x++) {
+            vertical += diff(x * 4, ((height - 1) * width + x) * 4);
+        }
+
+        const horizontalDiff = horizontal / height;
+        const verticalDiff = vertical / width;
+        return {
+            seamless: horizontalDiff <= tolerance && verticalDiff <= tolerance,
+            horizontalDiff,
+            verticalDiff
+        };
+    }
+
+    /**
+     * Stretch each channel's histogram to the full 0-255 range
+     */
+    normalize(imageData) {
+        const { data } = imageData;
+        const min = [255, 255, 255];
+        const max = [0, 0, 0];
+
+        for (let i = 0; i < data.length; i += 4) {
+            for (let c = 0; c < 3; c++) {
+                const value = data[i + c];
+                if (value < min[c]) min[c] = value;
+                if (value > max[c]) max[c] = value;
+            }
+        }
+
+        for (let c = 0; c < 3; c++) {
+            const range = max[c] - min[c];
+            if (range === 0 || range === 255) continue;
+
+            const scale = 255 / range;
+            for (let i = c; i < data.length; i += 4) {
+                data[i] = Math.round((data[i] - min[c]) * scale);
+            }
+        }
+    }
+
+    /**
+     * Separable Gaussian blur with sigma equal to the radius
+     */
+    gaussianBlur(imageData, radius) {
+        if (radius <= 0) return;
+
+        const { width, height, data } = imageData;
+        const size = Math.ceil(radius * 3);
+        const kernel = new Float32Array(size * 2 + 1);
+        let sum = 0;
+        for (let i = -size; i <= size; i++) {
+            kernel[i + size] = Math.exp(-(i * i) / (2 * radius * radius));
+            sum += kernel[i + size];
+        }
+        for (let i = 0; i < kernel.length; i++) {
+            kernel[i] /= sum;
+        }
+
+        // Horizontal pass into a float buffer, edges clamped
+        const temp = new Float32Array(width * height * 3);
+        for (let y = 0; y < height; y++) {
+            for (let x = 0; x < width; x++) {
+                let r = 0, g = 0, b = 0;
+                for (let k = -size; k <= size; k++) {
+                    const sx = Math.min(width - 1, Math.max(0, x + k));
+                    const idx = (y * width + sx) * 4;
+                    const weight = kernel[k + size];
+                    r += data[idx] * weight;
+                    g += data[idx + 1] * weight;
+                    b += data[idx + 2] * weight;
+                }
+                const t = (y * width + x) * 3;
+                temp[t] = r;
+                temp[t + 1] = g;
+                temp[t + 2] = b;
+            }
+        }
+
+        // Vertical pass back into the image
+        for (let y = 0; y < height; y++) {
+            for (let x = 0; x < width; x++) {
+                let r = 0, g = 0, b = 0;
+                for (let k = -size; k <= size; k++) {
+                    const sy = Math.min(height - 1, Math.max(0, y + k));
+                    const t = (sy * width + x) * 3;
+                    const weight = kernel[k + size];
+                    r += temp[t] * weight;
+                    g += temp[t + 1] * weight;
+                    b += temp[t + 2] * weight;
+                }
+                const idx = (y * width + x) * 4;
+                data[idx] = r;
+                data[idx + 1] = g;
+                data[idx + 2] = b;
+            }
         }
     }
 
+    /**
+     * Unsharp mask: push each pixel away from its blurred neighborhood
+     */
+    sharpen(imageData, amount, radius = 1) {
+        if (amount <= 0) return;
+
+        const { width, height, data } = imageData;
+        const blurred = new ImageData(new Uint8ClampedArray(data), width, height);
+        this.gaussianBlur(blurred, radius);
+
+        for (let i = 0; i < data.length; i += 4) {
+            for (let c = 0; c < 3; c++) {
+                data[i + c] = data[i + c] + amount * (data[i + c] - blurred.data[i + c]);
+            }
+        }
+    }
+
+    /**
+     * Reduce the image to a median-cut palette of the given size
+     */
+    quantize(imageData, colors) {
+        const { data } = imageData;
+        const pixelCount = data.length / 4;
+
+        // Sample at most ~50k pixels to build the palette
+        const stride = Math.max(1, Math.floor(pixelCount / 50000));
+        const samples = [];
+        for (let i = 0; i < pixelCount; i += stride) {
+            const idx = i * 4;
+            samples.push([data[idx], data[idx + 1], data[idx + 2]]);
+        }
+
+        const channelRange = (box, c) => {
+            let min = 255, max = 0;
+            for (const p of box) {
+                if (p[c] < min) min = p[c];
+                if (p[c] > max) max = p[c];
+            }
+            return max - min;
+        };
+
+        // Repeatedly split the box with the widest channel at its median
+        let boxes = [samples];
+        while (boxes.length < colors) {
+            let best = null;
+            for (const box of boxes) {
+                if (box.length < 2) continue;
+                for (let c = 0; c < 3; c++) {
+                    const range = channelRange(box, c);
+                    if (!best || range > best.range) best = { box, c, range };
+                }
+            }
+            if (!best || best.range === 0) break;
+
+            best.box.sort((a, b) => a[best.c] - b[best.c]);
+            const mid = Math.floor(best.box.length / 2);
+            boxes = boxes.filter(box => box !== best.box);
+            boxes.push(best.box.slice(0, mid), best.box.slice(mid));
+        }
+
+        const palette = boxes.map(box => {
+            const sum = [0, 0, 0];
+            for (const p of box) {
+                sum[0] += p[0];
+                sum[1] += p[1];
+                sum[2] += p[2];
+            }
+            return sum.map(v => Math.round(v / box.length));
+        });
+
+        // Map every pixel to its nearest palette entry
+        const cache = new Map();
+        for (let idx = 0; idx < data.length; idx += 4) {
+            const key = (data[idx] << 16) | (data[idx + 1] << 8) | data[idx + 2];
+            let color = cache.get(key);
+            if (!color) {
+                let bestDist = Infinity;
+                for (const entry of palette) {
+                    const dr = data[idx] - entry[0];
+                    const dg = data[idx + 1] - entry[1];
+                    const db = data[idx + 2] - entry[2];
+                    const dist = dr * dr + dg * dg + db * db;
+                    if (dist < bestDist) {
+                        bestDist = dist;
+                        color = entry;
+                    }
+                }
+                cache.set(key, color);
+            }
+            data[idx] = color[0];
+            data[idx + 1] = color[1];
+            data[idx + 2] = color[2];
+        }
+    }
+
+    /**
+     * Mix the original back in: 0 keeps the original, 1 leaves imageData as it is
+     */
+    blend(imageData, original, opacity) {
+        const { data } = imageData;
+        const source = original.data;
+        const amount = Math.min(1, Math.max(0, opacity));
+
+        for (let i = 0; i < data.length; i++) {
+            data[i] = source[i] + (data[i] - source[i]) * amount;
+        }
+    }
+
+    /**
+     * Offset red and blue horizontally in opposite directions relative to green
+     * @param {number} offset - Pixels; negative flips the direction
+     */
+    shiftChannels(imageData, offset) {
+        const { width, height, data } = imageData;
+        const source = new Uint8ClampedArray(data);
+
+        for (let y = 0; y < height; y++) {
+            const row = y * width;
+            for (let x = 0; x < width; x++) {
+                const redX = Math.min(width - 1, Math.max(0, x - offset));
+                const blueX = Math.min(width - 1, Math.max(0, x + offset));
+                const idx = (row + x) * 4;
+                data[idx] = source[(row + redX) * 4];
+                data[idx + 2] = source[(row + blueX) * 4 + 2];
+            }
+        }
+    }
+
+    /**
+     * Blend seeded monochrome noise over the image
+     * @param {number} intensity - 0 to 100
+     */
+    addGrain(imageData, intensity, seed) {
+        const { data } = imageData;
+        const random = this.createRng(seed);
+        const amplitude = (intensity / 100) * 64;
+
+        for (let i = 0; i < data.length; i += 4) {
+            // Sum of two uniforms gives a softer, bell-shaped grain
+            const noise = (random() + random() - 1) * amplitude;
+            data[i] += noise;
+            data[i + 1] += noise;
+            data[i + 2] += noise;
+        }
+    }
+
+    /**
+     * Small deterministic xorshift32 generator returning floats in [0, 1)
+     */
+    createRng(seed) {
+        let state = (seed >>> 0) || 0x9e3779b9;
+        return () => {
+            state ^= state << 13;
+            state >>>= 0;
+            state ^= state >>> 17;
+            state ^= state << 5;
+            state >>>= 0;
+            return state / 4294967296;
+        };
+    }
+
     /**
      * Apply hue shift to entire image
      */
@@ -250,10 +857,9 @@ class PixelSorter {
     }
 
     /**
-     * Shift hue of a single pixel
+     * Convert a pixel to HSV with h in degrees and s, v in 0-1
      */
-    shiftPixelHue(pixel, hueShift) {
-        // Convert RGB to HSV
+    rgbToHsv(pixel) {
         const r = pixel.r / 255;
         const g = pixel.g / 255;
         const b = pixel.b / 255;
@@ -275,8 +881,14 @@ class PixelSorter {
 
         if (h < 0) h += 360;
 
-        const s = max === 0 ? 0 : delta / max;
-        const v = max;
+        return { h, s: max === 0 ? 0 : delta / max, v: max };
+    }
+
+    /**
+     * Shift hue of a single pixel
+     */
+    shiftPixelHue(pixel, hueShift) {
+        let { h, s, v } = this.rgbToHsv(pixel);
 
         // Apply hue shift
         h = (h + hueShift) % 360;
@@ -310,6 +922,55 @@ class PixelSorter {
     }
 }
 
+PixelSorter.defaultParams = {
+    threshold: 0,
+    hueShift: 0,
+    sortMode: 'Brightness',
+    reverse: false,
+    angle: 45,
+    invertIntervals: false,
+    intervalMode: 'Threshold',
+    randomSeed: 1,
+    randomLength: 32,
+    passes: 1,
+    opacity: 1,
+    minSegment: 0,
+    maxSegment: 0,
+    normalize: false,
+    blurRadius: 0,
+    sharpenAmount: 0,
+    paletteColors: 0,
+    grainIntensity: 0,
+    grainSeed: 1,
+    channelShift: 0
+};
+
+PixelSorter.builtInMacros = [
+    {
+        name: 'Soft Drip',
+        steps: [
+            { effect: 'Blur', params: { blurRadius: 2 } },
+            { sort: 'Vertical', params: { threshold: 40 } }
+        ]
+    },
+    {
+        name: 'Glitch',
+        steps: [
+            { sort: 'Horizontal', params: { threshold: 80 } },
+            { effect: 'Channel Shift', params: { channelShift: 6 } },
+            { effect: 'Grain', params: { grainIntensity: 25 } }
+        ]
+    },
+    {
+        name: 'Poster',
+        steps: [
+            { effect: 'Normalize' },
+            { sort: 'Diagonal', params: { threshold: 60 } },
+            { effect: 'Palette', params: { paletteColors: 8 } }
+        ]
+    }
+];
+
 // Export for use in app.js
 if (typeof module !== 'undefined' && module.exports) {
     module.exports = PixelSorter;
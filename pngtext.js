@@ -0,0 +1,94 @@
+// PNG tEXt chunk helpers for self-describing exports
+class PngText {
+    /**
+     * Insert tEXt chunks right after the IHDR chunk
+     * @param {Uint8Array} png - Encoded PNG bytes
+     * @param {Object} entries - Keyword/value pairs (Latin-1)
+     * @returns {Uint8Array} - PNG bytes with the chunks added
+     */
+    static embed(png, entries) {
+        // Signature (8) + IHDR length, type, data (13) and CRC
+        const ihdrEnd = 8 + 4 + 4 + 13 + 4;
+        const chunks = Object.entries(entries).map(([keyword, value]) =>
+            PngText.createChunk('tEXt', `${keyword}\0${value}`)
+        );
+
+        const total = png.length + chunks.reduce((sum, chunk) => sum + chunk.length, 0);
+        const result = new Uint8Array(total);
+        result.set(png.subarray(0, ihdrEnd), 0);
+
+        let offset = ihdrEnd;
+        for (const chunk of chunks) {
+            result.set(chunk, offset);
+            offset += chunk.length;
+        }
+        result.set(png.subarray(ihdrEnd), offset);
+        return result;
+    }
+
+    /**
+     * Read every tEXt chunk back out of a PNG
+     * @param {Uint8Array} png - Encoded PNG bytes
+     * @returns {Object} - Keyword/value pairs
+     */
+    static read(png) {
+        const view = new DataView(png.buffer, png.byteOffset, png.byteLength);
+        const entries = {};
+        let offset = 8;
+
+        while (offset + 8 <= png.length) {
+            const length = view.getUint32(offset);
+            const type = String.fromCharCode(...png.subarray(offset + 4, offset + 8));
+            if (type === 'tEXt') {
+                const text = String.fromCharCode(...png.subarray(offset + 8, offset + 8 + length));
+                const separator = text.indexOf('\0');
+                entries[text.slice(0, separator)] = text.slice(separator + 1);
+            }
+            if (type === 'IEND') break;
+            offset += 12 + length;
+        }
+
+        return entries;
+    }
+
+    static createChunk(type, text) {
+        const data = new Uint8Array(text.length);
+        for (let i = 0; i < text.length; i++) {
+            data[i] = text.charCodeAt(i) & 0xff;
+        }
+
+        const chunk = new Uint8Array(12 + data.length);
+        const view = new DataView(chunk.buffer);
+        view.setUint32(0, data.length);
+        for (let i = 0; i < 4; i++) {
+            chunk[4 + i] = type.charCodeAt(i);
+        }
+        chunk.set(data, 8);
+        view.setUint32(8 + data.length, PngText.crc32(chunk.subarray(4, 8 + data.length)));
+        return chunk;
+    }
+
+    static crc32(bytes) {
+        if (!PngText.crcTable) {
+            PngText.crcTable = new Uint32Array(256);
+            for (let n = 0; n < 256; n++) {
+                let c = n;
+                for (let k = 0; k < 8; k++) {
+                    c = c & 1 ? 0xedb88320 ^ (c >>> 1) : c >>> 1;
+                }
+                PngText.crcTable[n] = c >>> 0;
+            }
+        }
+
+        let crc = 0xffffffff;
+        for (let i = 0; i < bytes.length; i++) {
+            crc = PngText.crcTable[(crc ^ bytes[i]) & 0xff] ^ (crc >>> 8);
+        }
+        return (crc ^ 0xffffffff) >>> 0;
+    }
+}
+
+// Export for use in app.js
+if (typeof module !== 'undefined' && module.exports) {
+    module.exports = PngText;
+}
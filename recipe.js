@@ -0,0 +1,32 @@
+// Settings recipes: the JSON files written by Save Settings and as save sidecars
+class Recipe {
+    /**
+     * Wrap settings in a recipe
+     * @param {Object} settings - Settings as returned by currentSettings
+     * @param {Object} extra - Additional fields, e.g. the image a sidecar belongs to
+     * @returns {Object} - Recipe ready for JSON.stringify
+     */
+    static create(settings, extra = {}) {
+        return {
+            app: 'harpy-pixelsort',
+            version: 1,
+            ...extra,
+            settings
+        };
+    }
+
+    /**
+     * Read the settings back out of a recipe file
+     * @param {string} text - Recipe JSON, or a bare settings object
+     * @returns {Object} - Settings for applySettings
+     */
+    static parse(text) {
+        const recipe = JSON.parse(text);
+        return recipe.settings || recipe;
+    }
+}
+
+// Export for use in app.js
+if (typeof module !== 'undefined' && module.exports) {
+    module.exports = Recipe;
+}
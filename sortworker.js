@@ -0,0 +1,16 @@
+// Runs the pixel sorter off the page's main thread
+importScripts('pixelsorter.js');
+
+const sorter = new PixelSorter();
+
+self.onmessage = (event) => {
+    const { width, height, buffer, algorithm, params } = event.data;
+    const imageData = new ImageData(new Uint8ClampedArray(buffer), width, height);
+
+    const result = sorter.sortPixelsWithProgress(imageData, algorithm, params, fraction => {
+        self.postMessage({ type: 'progress', fraction });
+        return true;
+    });
+
+    self.postMessage({ type: 'done', width, height, buffer: result.data.buffer }, [result.data.buffer]);
+};
@@ -0,0 +1,48 @@
+// Golden-image regression tests for the sort algorithms.
+// Regenerate the expected images after an intentional change with:
+//   UPDATE_GOLDENS=1 npm test
+const test = require('node:test');
+const assert = require('node:assert');
+const fs = require('fs');
+const path = require('path');
+const png = require('./png.js');
+
+require('./imagedata.js');
+const PixelSorter = require('../pixelsorter.js');
+
+const FIXTURE = path.join(__dirname, 'fixtures', 'input.png');
+const GOLDEN_DIR = path.join(__dirname, 'golden');
+const UPDATE = process.env.UPDATE_GOLDENS === '1';
+
+const CASES = {
+    threshold20: { threshold: 20 },
+    threshold40_white: { threshold: 40, sortMode: 'White' },
+    threshold80_hue90: { threshold: 80, hueShift: 90, sortMode: 'Black' },
+    threshold30_huekey: { threshold: 30, sortMode: 'Hue' },
+    threshold40_reverse: { threshold: 40, reverse: true }
+};
+
+const sorter = new PixelSorter();
+const input = png.decode(fs.readFileSync(FIXTURE));
+
+for (const algorithm of sorter.algorithms) {
+    for (const [name, params] of Object.entries(CASES)) {
+        const file = path.join(GOLDEN_DIR, `${algorithm.toLowerCase()}_${name}.png`);
+
+        test(`${algorithm} ${name} matches golden`, () => {
+            const source = new ImageData(new Uint8ClampedArray(input.data), input.width, input.height);
+            const result = sorter.sortPixels(source, algorithm, params);
+
+            if (UPDATE) {
+                fs.writeFileSync(file, png.encode(result.width, result.height, result.data));
+                return;
+            }
+
+            assert.ok(fs.existsSync(file), `missing golden ${path.basename(file)}; run with UPDATE_GOLDENS=1`);
+            const expected = png.decode(fs.readFileSync(file));
+            assert.strictEqual(result.width, expected.width);
+            assert.strictEqual(result.height, expected.height);
+            assert.deepStrictEqual(Buffer.from(result.data), Buffer.from(expected.data));
+        });
+    }
+}
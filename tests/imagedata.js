@@ -0,0 +1,10 @@
+// Canvas ImageData is not available in Node; require this before pixelsorter.js
+if (typeof globalThis.ImageData === 'undefined') {
+    globalThis.ImageData = class ImageData {
+        constructor(data, width, height) {
+            this.data = data;
+            this.width = width;
+            this.height = height;
+        }
+    };
+}
@@ -0,0 +1,86 @@
+// Tests for carrying EXIF from a JPEG source into JPEG exports
+const test = require('node:test');
+const assert = require('node:assert');
+
+const JpegExif = require('../jpegexif.js');
+
+const { TAGS } = JpegExif;
+
+/**
+ * Little-endian APP1 segment shaped like a phone photo: rotated, with timestamps,
+ * dimensions, a GPS IFD and an IFD1 thumbnail
+ */
+function cameraSegment() {
+    const bytes = [];
+    const u16 = v => bytes.push(v & 0xff, v >> 8);
+    const u32 = v => bytes.push(v & 0xff, (v >> 8) & 0xff, (v >> 16) & 0xff, v >>> 24);
+    const text = s => bytes.push(...Array.from(s, c => c.charCodeAt(0)), 0);
+    const entry = (tag, type, count, value) => { u16(tag); u16(type); u32(count); u32(value); };
+
+    // Offsets are relative to the TIFF header: IFD0 at 8 (5 entries), Exif IFD at 74
+    // (3 entries), GPS IFD at 116 (1 entry), IFD1 at 134 (1 entry), strings from 152
+    bytes.push(0x49, 0x49); u16(42); u32(8);
+    u16(5);
+    entry(TAGS.Orientation, 3, 1, 6);
+    entry(TAGS.DateTime, 2, 20, 152);
+    entry(0x0110, 2, 8, 172);
+    entry(TAGS.ExifIFD, 4, 1, 74);
+    entry(TAGS.GPSIFD, 4, 1, 116);
+    u32(134);
+    u16(3);
+    entry(TAGS.DateTimeOriginal, 2, 20, 180);
+    entry(0xa002, 4, 1, 4032);
+    entry(0xa003, 4, 1, 3024);
+    u32(0);
+    u16(1);
+    entry(0x0001, 2, 2, 0x4e);
+    u32(0);
+    u16(1);
+    entry(0x0201, 4, 1, 0);
+    u32(0);
+    text('2024:05:01 18:30:00');
+    text('Phone 7');
+    text('2024:05:01 18:29:58');
+
+    const length = bytes.length + 8;
+    return Uint8Array.from([0xff, 0xe1, length >> 8, length & 0xff, 0x45, 0x78, 0x69, 0x66, 0, 0, ...bytes]);
+}
+
+// A JFIF APP0, a stub scan and EOI
+const JFIF = [0xff, 0xe0, 0x00, 0x07, 0x4a, 0x46, 0x49, 0x46, 0x00];
+const SCAN = [0xff, 0xda, 0x00, 0x02, 0x12, 0x34, 0xff, 0xd9];
+
+test('the fixture carries location, thumbnail and dimensions', () => {
+    const tags = JpegExif.parse(cameraSegment());
+    assert.strictEqual(tags.gps[0x0001], 'N');
+    assert.strictEqual(tags.exif[0xa002], 4032);
+    assert.strictEqual(tags.hasThumbnail, true);
+});
+
+test('only the timestamps and an upright orientation are exported', () => {
+    const source = Uint8Array.from([0xff, 0xd8, ...cameraSegment(), ...SCAN]);
+    const tags = JpegExif.parse(JpegExif.fromSource(source));
+
+    assert.deepStrictEqual(tags.ifd0, {
+        [TAGS.Orientation]: 1,
+        [TAGS.DateTime]: '2024:05:01 18:30:00',
+        [TAGS.ExifIFD]: tags.ifd0[TAGS.ExifIFD]
+    });
+    assert.deepStrictEqual(tags.exif, { [TAGS.DateTimeOriginal]: '2024:05:01 18:29:58' });
+    assert.deepStrictEqual(tags.gps, {});
+    assert.strictEqual(tags.hasThumbnail, false);
+});
+
+test('the exported segment lands after the JFIF header', () => {
+    const segment = JpegExif.create({ dateTime: '2024:05:01 18:30:00' });
+    const output = Uint8Array.from([0xff, 0xd8, ...JFIF, ...SCAN]);
+
+    const embedded = JpegExif.embed(output, segment);
+    assert.deepStrictEqual(embedded, Uint8Array.from([0xff, 0xd8, ...JFIF, ...segment, ...SCAN]));
+    assert.deepStrictEqual(JpegExif.extract(embedded), segment);
+});
+
+test('sources without EXIF yield nothing', () => {
+    assert.strictEqual(JpegExif.fromSource(Uint8Array.from([0xff, 0xd8, ...JFIF, ...SCAN])), null);
+    assert.strictEqual(JpegExif.fromSource(Uint8Array.from([0x89, 0x50, 0x4e, 0x47])), null);
+});
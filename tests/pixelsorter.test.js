@@ -0,0 +1,227 @@
+// Unit tests for individual PixelSorter behaviours
+const test = require('node:test');
+const assert = require('node:assert');
+
+require('./imagedata.js');
+const PixelSorter = require('../pixelsorter.js');
+
+const sorter = new PixelSorter();
+
+/**
+ * Image whose pixels all differ, so any lost or duplicated pixel shows up
+ */
+function uniqueImage(width, height) {
+    const data = new Uint8ClampedArray(width * height * 4);
+    for (let i = 0; i < width * height; i++) {
+        data[i * 4] = i & 0xff;
+        data[i * 4 + 1] = (i >> 8) & 0xff;
+        data[i * 4 + 2] = (i * 37) & 0xff;
+        data[i * 4 + 3] = 255;
+    }
+    return new ImageData(data, width, height);
+}
+
+function pixelSet(imageData) {
+    const pixels = [];
+    for (let i = 0; i < imageData.data.length; i += 4) {
+        pixels.push(imageData.data.subarray(i, i + 4).join());
+    }
+    return pixels.sort();
+}
+
+test('diagonal sort keeps every pixel at any angle', () => {
+    const source = uniqueImage(23, 17);
+    for (const angle of [0, 10, 30, 45, 70, 90, 135, 200, 315, 359]) {
+        const result = sorter.sortPixels(source, 'Diagonal', { angle, threshold: 255 });
+        assert.deepStrictEqual(pixelSet(result), pixelSet(source), `angle ${angle}`);
+    }
+});
+
+test('diagonal sort at 0 and 90 degrees matches horizontal and vertical', () => {
+    const source = uniqueImage(23, 17);
+    const params = { threshold: 60 };
+    assert.deepStrictEqual(
+        sorter.sortPixels(source, 'Diagonal', { ...params, angle: 0 }).data,
+        sorter.sortPixels(source, 'Horizontal', params).data
+    );
+    assert.deepStrictEqual(
+        sorter.sortPixels(source, 'Diagonal', { ...params, angle: 90 }).data,
+        sorter.sortPixels(source, 'Vertical', params).data
+    );
+});
+
+test('inverted intervals select the noisy part of a row', () => {
+    // Smooth gradient followed by alternating dark/bright noise
+    const row = [0, 2, 4, 6, 8, 10, 200, 20, 220, 40, 240].map(v => ({ r: v, g: v, b: v, a: 255 }));
+    const params = { ...PixelSorter.defaultParams, threshold: 10 };
+
+    assert.deepStrictEqual(sorter.findIntervals(row, params), [[0, 6]]);
+    assert.deepStrictEqual(sorter.findIntervals(row, { ...params, invertIntervals: true }), [[5, 11]]);
+});
+
+test('segment limits drop short intervals and split long ones', () => {
+    const intervals = [[0, 2], [4, 7], [10, 20]];
+    const params = { ...PixelSorter.defaultParams };
+
+    assert.deepStrictEqual(sorter.limitIntervals(intervals, params), intervals);
+    assert.deepStrictEqual(sorter.limitIntervals(intervals, { ...params, minSegment: 3 }), [[4, 7], [10, 20]]);
+    assert.deepStrictEqual(
+        sorter.limitIntervals(intervals, { ...params, maxSegment: 4 }),
+        [[0, 2], [4, 7], [10, 14], [14, 18], [18, 20]]
+    );
+});
+
+test('edge mode breaks intervals at the Sobel edges of the image', () => {
+    // Left half dark, right half bright: one vertical edge down the middle
+    const width = 8;
+    const height = 4;
+    const data = new Uint8ClampedArray(width * height * 4);
+    for (let i = 0; i < width * height; i++) {
+        const v = i % width < 4 ? 10 : 240;
+        data.set([v, v, v, 255], i * 4);
+    }
+    const image = new ImageData(data, width, height);
+
+    const params = sorter.prepareSortParams(image, { ...PixelSorter.defaultParams, intervalMode: 'Edges', threshold: 50 });
+    const row = [...Array(width).keys()];
+    const pixels = row.map(i => ({ r: data[i * 4], g: data[i * 4 + 1], b: data[i * 4 + 2], a: 255 }));
+
+    assert.deepStrictEqual(sorter.findIntervals(pixels, params, row), [[0, 3], [4, 8]]);
+});
+
+test('random intervals are reproducible for a seed', () => {
+    const source = uniqueImage(23, 17);
+    const params = { intervalMode: 'Random', randomSeed: 42, randomLength: 6 };
+
+    const first = sorter.sortPixels(source, 'Horizontal', params);
+    const second = sorter.sortPixels(source, 'Horizontal', params);
+    const reseeded = sorter.sortPixels(source, 'Horizontal', { ...params, randomSeed: 43 });
+
+    assert.deepStrictEqual(first.data, second.data);
+    assert.notDeepStrictEqual(first.data, reseeded.data);
+});
+
+test('zero passes leaves the preprocessed input unsorted', () => {
+    const source = uniqueImage(23, 17);
+    const hueShifted = sorter.sortPixels(source, 'Horizontal', { hueShift: 90, passes: 0 });
+
+    const expected = new ImageData(new Uint8ClampedArray(source.data), source.width, source.height);
+    sorter.applyHueShift(expected, 90);
+    assert.deepStrictEqual(hueShifted.data, expected.data);
+});
+
+test('extra passes keep every pixel', () => {
+    const source = uniqueImage(23, 17);
+    const result = sorter.sortPixels(source, 'Radial', { threshold: 40, passes: 50 });
+    assert.deepStrictEqual(pixelSet(result), pixelSet(source));
+});
+
+test('progress is reported up to completion', () => {
+    const source = uniqueImage(23, 17);
+    const reported = [];
+    const result = sorter.sortPixelsWithProgress(source, 'Vertical', { threshold: 40 }, fraction => {
+        reported.push(fraction);
+        return true;
+    });
+
+    assert.strictEqual(reported[0], 0);
+    assert.strictEqual(reported[reported.length - 1], 1);
+    assert.ok(reported.every((fraction, i) => i === 0 || fraction >= reported[i - 1]));
+    assert.deepStrictEqual(result.data, sorter.sortPixels(source, 'Vertical', { threshold: 40 }).data);
+});
+
+test('returning false from progress aborts with the original image', () => {
+    const source = uniqueImage(23, 17);
+    let calls = 0;
+    const result = sorter.sortPixelsWithProgress(source, 'Horizontal', { threshold: 255, hueShift: 90 }, fraction => {
+        calls++;
+        return fraction < 0.5;
+    });
+
+    assert.deepStrictEqual(result.data, source.data);
+    assert.ok(calls > 1);
+});
+
+test('opacity blends the sorted result with the original', () => {
+    const source = uniqueImage(23, 17);
+    const params = { threshold: 80, hueShift: 45 };
+
+    const none = sorter.sortPixels(source, 'Horizontal', { ...params, opacity: 0 });
+    const full = sorter.sortPixels(source, 'Horizontal', { ...params, opacity: 1 });
+    const half = sorter.sortPixels(source, 'Horizontal', { ...params, opacity: 0.5 });
+
+    assert.deepStrictEqual(none.data, source.data);
+    assert.deepStrictEqual(full.data, sorter.sortPixels(source, 'Horizontal', params).data);
+    for (let i = 0; i < half.data.length; i++) {
+        assert.ok(Math.abs(half.data[i] - (source.data[i] + full.data[i]) / 2) <= 0.5);
+    }
+});
+
+test('macros record and replay the opacity blend', () => {
+    const source = uniqueImage(23, 17);
+    const params = { threshold: 80, opacity: 0.5 };
+    const macro = sorter.createMacro('Half', 'Horizontal', params);
+
+    assert.ok(macro.steps.some(step => step.effect === 'Opacity'));
+    assert.deepStrictEqual(sorter.runMacro(source, macro).data, sorter.sortPixels(source, 'Horizontal', params).data);
+});
+
+test('normalize leaves a full-range image unchanged', () => {
+    const source = uniqueImage(16, 16);
+    // Give every channel both extremes
+    source.data.set([0, 0, 0, 255], 0);
+    source.data.set([255, 255, 255, 255], 4);
+
+    const result = new ImageData(new Uint8ClampedArray(source.data), source.width, source.height);
+    sorter.normalize(result);
+    assert.deepStrictEqual(result.data, source.data);
+});
+
+test('sharpen with zero strength leaves the image unchanged', () => {
+    const source = uniqueImage(23, 17);
+
+    const result = new ImageData(new Uint8ClampedArray(source.data), source.width, source.height);
+    sorter.sharpen(result, 0);
+    assert.deepStrictEqual(result.data, source.data);
+
+    sorter.sharpen(result, 1);
+    assert.notDeepStrictEqual(result.data, source.data);
+});
+
+test('seamless check tells tileable edges from a seam', () => {
+    const width = 16;
+    const height = 12;
+    const image = (value) => {
+        const data = new Uint8ClampedArray(width * height * 4);
+        for (let y = 0; y < height; y++) {
+            for (let x = 0; x < width; x++) {
+                const v = value(x, y);
+                data.set([v, v, v, 255], (y * width + x) * 4);
+            }
+        }
+        return new ImageData(data, width, height);
+    };
+
+    // Mirrored around the centre, so opposite edges match exactly
+    const tileable = sorter.checkSeamless(image((x, y) => Math.abs(2 * x - width + 1) * 8 + Math.abs(2 * y - height + 1) * 4));
+    assert.deepStrictEqual(tileable, { seamless: true, horizontalDiff: 0, verticalDiff: 0 });
+
+    // A left-to-right gradient wraps from black straight to white
+    const seamed = sorter.checkSeamless(image(x => x * 17));
+    assert.strictEqual(seamed.seamless, false);
+    assert.strictEqual(seamed.horizontalDiff, 255);
+    assert.strictEqual(seamed.verticalDiff, 0);
+});
+
+test('registered sort keys join the mode cycle and sort like built-ins', () => {
+    const custom = new PixelSorter();
+    custom.registerSortKey('Red', pixel => pixel.r);
+    assert.strictEqual(custom.modes[custom.modes.length - 1], 'Red');
+
+    const source = uniqueImage(23, 17);
+    const params = { threshold: 60 };
+    assert.deepStrictEqual(
+        custom.sortPixels(source, 'Horizontal', { ...params, sortMode: 'Red' }).data,
+        custom.sortPixels(source, 'Horizontal', { ...params, sortMode: 'Black' }).data
+    );
+});
@@ -0,0 +1,124 @@
+// Minimal 8-bit PNG reader/writer for the golden-image tests
+const zlib = require('zlib');
+const PngText = require('../pngtext.js');
+
+const SIGNATURE = Buffer.from([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a]);
+
+/**
+ * Decode a non-interlaced 8-bit RGB or RGBA PNG
+ * @param {Buffer} buffer - PNG file contents
+ * @returns {Object} - { width, height, data } with RGBA data
+ */
+function decode(buffer) {
+    if (!buffer.subarray(0, 8).equals(SIGNATURE)) {
+        throw new Error('Not a PNG file');
+    }
+
+    let width = 0;
+    let height = 0;
+    let channels = 0;
+    const idat = [];
+
+    let offset = 8;
+    while (offset < buffer.length) {
+        const length = buffer.readUInt32BE(offset);
+        const type = buffer.toString('latin1', offset + 4, offset + 8);
+        const body = buffer.subarray(offset + 8, offset + 8 + length);
+
+        if (type === 'IHDR') {
+            width = body.readUInt32BE(0);
+            height = body.readUInt32BE(4);
+            const bitDepth = body[8];
+            const colorType = body[9];
+            if (bitDepth !== 8 || (colorType !== 2 && colorType !== 6) || body[12] !== 0) {
+                throw new Error('Only 8-bit non-interlaced RGB/RGBA PNGs are supported');
+            }
+            channels = colorType === 6 ? 4 : 3;
+        } else if (type === 'IDAT') {
+            idat.push(body);
+        } else if (type === 'IEND') {
+            break;
+        }
+        offset += 12 + length;
+    }
+
+    const raw = zlib.inflateSync(Buffer.concat(idat));
+    const stride = width * channels;
+    const pixels = Buffer.alloc(stride * height);
+
+    for (let y = 0; y < height; y++) {
+        const filter = raw[y * (stride + 1)];
+        const line = raw.subarray(y * (stride + 1) + 1, (y + 1) * (stride + 1));
+        const out = y * stride;
+        const prev = out - stride;
+
+        for (let x = 0; x < stride; x++) {
+            const a = x >= channels ? pixels[out + x - channels] : 0;
+            const b = y > 0 ? pixels[prev + x] : 0;
+            const c = x >= channels && y > 0 ? pixels[prev + x - channels] : 0;
+            let value = line[x];
+
+            switch (filter) {
+                case 1: value += a; break;
+                case 2: value += b; break;
+                case 3: value += (a + b) >> 1; break;
+                case 4: {
+                    const p = a + b - c;
+                    const pa = Math.abs(p - a);
+                    const pb = Math.abs(p - b);
+                    const pc = Math.abs(p - c);
+                    value += pa <= pb && pa <= pc ? a : pb <= pc ? b : c;
+                    break;
+                }
+            }
+            pixels[out + x] = value & 0xff;
+        }
+    }
+
+    const data = new Uint8ClampedArray(width * height * 4);
+    for (let i = 0; i < width * height; i++) {
+        data[i * 4] = pixels[i * channels];
+        data[i * 4 + 1] = pixels[i * channels + 1];
+        data[i * 4 + 2] = pixels[i * channels + 2];
+        data[i * 4 + 3] = channels === 4 ? pixels[i * channels + 3] : 255;
+    }
+
+    return { width, height, data };
+}
+
+/**
+ * Encode RGBA pixels as a PNG
+ * @returns {Buffer} - PNG file contents
+ */
+function encode(width, height, data) {
+    const stride = width * 4;
+    const raw = Buffer.alloc((stride + 1) * height);
+    for (let y = 0; y < height; y++) {
+        raw[y * (stride + 1)] = 0;
+        Buffer.from(data.buffer, data.byteOffset + y * stride, stride).copy(raw, y * (stride + 1) + 1);
+    }
+
+    const ihdr = Buffer.alloc(13);
+    ihdr.writeUInt32BE(width, 0);
+    ihdr.writeUInt32BE(height, 4);
+    ihdr[8] = 8;
+    ihdr[9] = 6;
+
+    return Buffer.concat([
+        SIGNATURE,
+        chunk('IHDR', ihdr),
+        chunk('IDAT', zlib.deflateSync(raw)),
+        chunk('IEND', Buffer.alloc(0))
+    ]);
+}
+
+function chunk(type, body) {
+    const out = Buffer.alloc(12 + body.length);
+    out.writeUInt32BE(body.length, 0);
+    out.write(type, 4, 'latin1');
+    body.copy(out, 8);
+    out.writeUInt32BE(PngText.crc32(out.subarray(4, 8 + body.length)), 8 + body.length);
+    return out;
+}
+
+module.exports = { decode, encode };
@@ -0,0 +1,32 @@
+// Tests for the settings metadata embedded in PNG exports
+const test = require('node:test');
+const assert = require('node:assert');
+
+const PngText = require('../pngtext.js');
+const png = require('./png.js');
+
+test('tEXt chunks survive an embed/read round trip', () => {
+    const data = new Uint8Array(3 * 2 * 4).map((_, i) => i * 7);
+    const encoded = new Uint8Array(png.encode(3, 2, data));
+    const entries = {
+        Software: 'Harpy Pixelsort',
+        'pixelsort:settings': JSON.stringify({ threshold: 40, sortMode: 'Hue', reverse: true })
+    };
+
+    const embedded = PngText.embed(encoded, entries);
+    assert.deepStrictEqual(PngText.read(embedded), entries);
+
+    // The pixels are untouched
+    const decoded = png.decode(Buffer.from(embedded));
+    assert.deepStrictEqual(new Uint8Array(decoded.data), data);
+});
+
+test('PNGs without tEXt chunks read as empty', () => {
+    const encoded = new Uint8Array(png.encode(1, 1, new Uint8Array(4)));
+    assert.deepStrictEqual(PngText.read(encoded), {});
+});
+
+test('chunk CRCs match the PNG specification', () => {
+    // Every PNG ends with the same IEND chunk
+    assert.strictEqual(PngText.crc32(Uint8Array.from('IEND', c => c.charCodeAt(0))), 0xae426082);
+});
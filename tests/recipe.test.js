@@ -0,0 +1,26 @@
+// Tests for the settings recipe files
+const test = require('node:test');
+const assert = require('node:assert');
+
+const Recipe = require('../recipe.js');
+
+const settings = {
+    algorithm: 'Diagonal',
+    threshold: 40,
+    sortMode: 'Hue',
+    reverse: true,
+    angle: 30,
+    opacity: 0.5
+};
+
+test('sidecar recipes read back the settings they were saved with', () => {
+    const sidecar = Recipe.create(settings, { image: 'edit_003_diagonal.png', savedAt: '2026-01-01T00:00:00.000Z' });
+    const text = JSON.stringify(sidecar, null, 2);
+
+    assert.strictEqual(JSON.parse(text).image, 'edit_003_diagonal.png');
+    assert.deepStrictEqual(Recipe.parse(text), settings);
+});
+
+test('bare settings objects are accepted as recipes', () => {
+    assert.deepStrictEqual(Recipe.parse(JSON.stringify(settings)), settings);
+});